@@ -162,12 +162,19 @@ impl From<ProvingOptions> for WinterProofOptions {
 ///
 /// - `max_cycles` specifies the maximum number of cycles a program is allowed to execute.
 /// - `expected_cycles` specifies the number of cycles a program is expected to execute.
+/// - `max_block_nesting_depth` specifies the maximum number of code blocks which can be nested
+///   within each other during execution.
+/// - `max_loop_repeats` specifies the maximum number of times the body of a single loop can be
+///   repeated.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ExecutionOptions {
     max_cycles: u32,
     expected_cycles: u32,
+    max_block_nesting_depth: usize,
+    max_loop_repeats: usize,
     enable_tracing: bool,
     enable_debugging: bool,
+    enable_decoder_op_log: bool,
 }
 
 impl Default for ExecutionOptions {
@@ -175,13 +182,22 @@ impl Default for ExecutionOptions {
         ExecutionOptions {
             max_cycles: u32::MAX,
             expected_cycles: MIN_TRACE_LEN as u32,
+            max_block_nesting_depth: Self::DEFAULT_MAX_BLOCK_NESTING_DEPTH,
+            max_loop_repeats: usize::MAX,
             enable_tracing: false,
             enable_debugging: false,
+            enable_decoder_op_log: false,
         }
     }
 }
 
 impl ExecutionOptions {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Default maximum number of code blocks which can be nested within each other.
+    pub const DEFAULT_MAX_BLOCK_NESTING_DEPTH: usize = 1 << 12;
+
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
 
@@ -212,11 +228,34 @@ impl ExecutionOptions {
         Ok(ExecutionOptions {
             max_cycles,
             expected_cycles,
+            max_block_nesting_depth: Self::DEFAULT_MAX_BLOCK_NESTING_DEPTH,
+            max_loop_repeats: usize::MAX,
             enable_tracing,
             enable_debugging,
+            enable_decoder_op_log: false,
         })
     }
 
+    /// Sets the maximum number of code blocks which can be nested within each other during
+    /// execution.
+    ///
+    /// Starting a block beyond this depth causes execution to fail. By default, at most
+    /// [ExecutionOptions::DEFAULT_MAX_BLOCK_NESTING_DEPTH] blocks can be nested.
+    pub fn with_max_block_nesting_depth(mut self, max_depth: usize) -> Self {
+        self.max_block_nesting_depth = max_depth;
+        self
+    }
+
+    /// Sets the maximum number of times the body of a single loop can be repeated during
+    /// execution.
+    ///
+    /// The first execution of a loop body is not counted as a repeat. Repeating the body more
+    /// times causes execution to fail. By default, the number of repeats is not limited.
+    pub fn with_max_loop_repeats(mut self, max_repeats: usize) -> Self {
+        self.max_loop_repeats = max_repeats;
+        self
+    }
+
     /// Enables execution of the `trace` instructions.
     pub fn with_tracing(mut self) -> Self {
         self.enable_tracing = true;
@@ -234,6 +273,16 @@ impl ExecutionOptions {
         self
     }
 
+    /// Enables recording of the calls made to the decoder during execution.
+    ///
+    /// The recorded log can be used to replay the decoding of a program without executing it
+    /// again. The log grows with every executed operation, and thus, recording is disabled by
+    /// default (including in debug mode).
+    pub fn with_decoder_op_log(mut self) -> Self {
+        self.enable_decoder_op_log = true;
+        self
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -251,6 +300,18 @@ impl ExecutionOptions {
         self.expected_cycles
     }
 
+    /// Returns the maximum number of code blocks which can be nested within each other during
+    /// execution.
+    pub fn max_block_nesting_depth(&self) -> usize {
+        self.max_block_nesting_depth
+    }
+
+    /// Returns the maximum number of times the body of a single loop can be repeated during
+    /// execution.
+    pub fn max_loop_repeats(&self) -> usize {
+        self.max_loop_repeats
+    }
+
     /// Returns a flag indicating whether the VM should execute `trace` instructions.
     pub fn enable_tracing(&self) -> bool {
         self.enable_tracing
@@ -260,4 +321,9 @@ impl ExecutionOptions {
     pub fn enable_debugging(&self) -> bool {
         self.enable_debugging
     }
+
+    /// Returns a flag indicating whether the VM should record the calls made to the decoder.
+    pub fn enable_decoder_op_log(&self) -> bool {
+        self.enable_decoder_op_log
+    }
 }
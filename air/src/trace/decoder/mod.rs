@@ -2,7 +2,7 @@ use core::ops::Range;
 
 use vm_core::{utils::range, Felt, Operation, ONE, ZERO};
 
-use super::DECODER_AUX_TRACE_OFFSET;
+use super::{chiplets::hasher::RATE_LEN, DECODER_AUX_TRACE_OFFSET, DECODER_TRACE_WIDTH};
 
 // CONSTANTS
 // ================================================================================================
@@ -25,7 +25,10 @@ pub const OP_BITS_RANGE: Range<usize> = range(OP_BITS_OFFSET, NUM_OP_BITS);
 pub const HASHER_STATE_OFFSET: usize = OP_BITS_RANGE.end;
 
 /// Number of hasher columns in the decoder trace.
-pub const NUM_HASHER_COLUMNS: usize = 8;
+///
+/// These columns hold the values absorbed into the rate portion of the hasher state, and thus,
+/// their number is the same as the hasher's rate width.
+pub const NUM_HASHER_COLUMNS: usize = RATE_LEN;
 
 /// Number of helper registers available to user ops.
 pub const NUM_USER_OP_HELPERS: usize = 6;
@@ -89,6 +92,25 @@ pub const IS_CALL_FLAG_COL_IDX: usize = HASHER_STATE_RANGE.start + 6;
 /// Index of a flag column which indicates whether an ending block is a SYSCALL block.
 pub const IS_SYSCALL_FLAG_COL_IDX: usize = HASHER_STATE_RANGE.start + 7;
 
+// --- Layout checks ------------------------------------------------------------------------------
+
+// The column ranges above must cover the decoder trace without gaps or overlaps, and the flag
+// columns used when ending a block must fall within the hasher state columns.
+const _: () = {
+    assert!(ADDR_COL_IDX == 0);
+    assert!(OP_BITS_RANGE.start == ADDR_COL_IDX + 1);
+    assert!(HASHER_STATE_RANGE.start == OP_BITS_RANGE.end);
+    assert!(IN_SPAN_COL_IDX == HASHER_STATE_RANGE.end);
+    assert!(GROUP_COUNT_COL_IDX == IN_SPAN_COL_IDX + 1);
+    assert!(OP_INDEX_COL_IDX == GROUP_COUNT_COL_IDX + 1);
+    assert!(OP_BATCH_FLAGS_RANGE.start == OP_INDEX_COL_IDX + 1);
+    assert!(OP_BITS_EXTRA_COLS_RANGE.start == OP_BATCH_FLAGS_RANGE.end);
+    assert!(OP_BITS_EXTRA_COLS_RANGE.end == DECODER_TRACE_WIDTH);
+
+    assert!(USER_OP_HELPERS_OFFSET + NUM_USER_OP_HELPERS == HASHER_STATE_RANGE.end);
+    assert!(IS_SYSCALL_FLAG_COL_IDX < HASHER_STATE_RANGE.end);
+};
+
 // --- Column accessors in the auxiliary columns --------------------------------------------------
 
 /// Running product column representing block stack table.
@@ -104,3 +126,28 @@ pub const P3_COL_IDX: usize = DECODER_AUX_TRACE_OFFSET + 2;
 pub const DECODER_OP_BITS_OFFSET: usize = super::DECODER_TRACE_OFFSET + OP_BITS_OFFSET;
 pub const DECODER_USER_OP_HELPERS_OFFSET: usize =
     super::DECODER_TRACE_OFFSET + USER_OP_HELPERS_OFFSET;
+
+// TESTS
+// ================================================================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_layout() {
+        assert_eq!(0, ADDR_COL_IDX);
+        assert_eq!(1..8, OP_BITS_RANGE);
+        assert_eq!(8..16, HASHER_STATE_RANGE);
+        assert_eq!(16, IN_SPAN_COL_IDX);
+        assert_eq!(17, GROUP_COUNT_COL_IDX);
+        assert_eq!(18, OP_INDEX_COL_IDX);
+        assert_eq!(19..22, OP_BATCH_FLAGS_RANGE);
+        assert_eq!(22..24, OP_BITS_EXTRA_COLS_RANGE);
+        assert_eq!(24, DECODER_TRACE_WIDTH);
+
+        // user operation helpers and block flags are located in the hasher state columns
+        assert_eq!(10, USER_OP_HELPERS_OFFSET);
+        assert_eq!(12, IS_LOOP_BODY_FLAG_COL_IDX);
+        assert_eq!(15, IS_SYSCALL_FLAG_COL_IDX);
+    }
+}
@@ -11,8 +11,8 @@ use miden_crypto::hash::rpo::RpoDigest;
 
 mod node;
 pub use node::{
-    BasicBlockNode, CallNode, DynNode, ExternalNode, JoinNode, LoopNode, MastNode, OpBatch,
-    OperationOrDecorator, SplitNode, OP_BATCH_SIZE, OP_GROUP_SIZE,
+    BasicBlockNode, BatchLayout, CallNode, DynNode, ExternalNode, JoinNode, LoopNode, MastNode,
+    OpBatch, OperationOrDecorator, SplitNode, OP_BATCH_SIZE, OP_GROUP_SIZE,
 };
 use winter_utils::{ByteWriter, DeserializationError, Serializable};
 
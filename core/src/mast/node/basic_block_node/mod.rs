@@ -11,8 +11,8 @@ use crate::{
 };
 
 mod op_batch;
-pub use op_batch::OpBatch;
 use op_batch::OpBatchAccumulator;
+pub use op_batch::{BatchLayout, OpBatch};
 
 #[cfg(test)]
 mod tests;
@@ -21,6 +21,9 @@ mod tests;
 // ================================================================================================
 
 /// Maximum number of operations per group.
+///
+/// Opcodes are [Operation::OP_BITS] bits wide, and thus, a group of 9 operations occupies 63 bits,
+/// which is the largest multiple of the opcode width fitting into a single field element.
 pub const GROUP_SIZE: usize = 9;
 
 /// Maximum number of groups per batch.
@@ -149,6 +152,14 @@ impl BasicBlockNode {
         self.op_batches.len()
     }
 
+    /// Returns the layout of each operation batch in this basic block.
+    ///
+    /// Only the last batch may be partially filled; the number of groups reported for it is the
+    /// actual number of groups, i.e., before it is rounded up to the next power of two.
+    pub fn batch_layout(&self) -> Vec<BatchLayout> {
+        self.op_batches.iter().map(OpBatch::layout).collect()
+    }
+
     /// Returns the total number of operation groups in this basic block.
     ///
     /// Then number of operation groups is computed as follows:
@@ -41,6 +41,37 @@ impl OpBatch {
     pub fn num_groups(&self) -> usize {
         self.num_groups
     }
+
+    /// Returns a summary of how operations in this batch are distributed across op groups.
+    pub fn layout(&self) -> BatchLayout {
+        // within the used groups, only groups holding immediate values have no operations
+        let imm_groups = self.op_counts[..self.num_groups]
+            .iter()
+            .enumerate()
+            .filter(|(_, &op_count)| op_count == 0)
+            .map(|(group_idx, _)| group_idx)
+            .collect();
+
+        BatchLayout {
+            num_groups: self.num_groups,
+            imm_groups,
+            num_ops: self.op_counts.iter().sum(),
+        }
+    }
+}
+
+// BATCH LAYOUT
+// ================================================================================================
+
+/// Describes how operations of an [OpBatch] are distributed across its op groups.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BatchLayout {
+    /// Number of groups used by the batch, including groups holding immediate values.
+    pub num_groups: usize,
+    /// Indexes of the groups holding immediate values.
+    pub imm_groups: Vec<usize>,
+    /// Number of non-decorator operations in the batch.
+    pub num_ops: usize,
 }
 
 // OPERATION BATCH ACCUMULATOR
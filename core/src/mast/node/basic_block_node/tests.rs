@@ -293,6 +293,57 @@ fn batch_ops() {
     assert_eq!(hasher::hash_elements(&all_groups), hash);
 }
 
+#[test]
+fn group_size_boundary() {
+    // all opcodes of a full group must fit into a single field element
+    assert!(GROUP_SIZE * Operation::OP_BITS < u64::BITS as usize);
+
+    // exactly GROUP_SIZE operations fill a single group
+    let ops = vec![Operation::Pad; GROUP_SIZE];
+    let (batches, _) = super::batch_and_hash_ops(ops.clone());
+    assert_eq!(1, batches.len());
+    assert_eq!(1, batches[0].num_groups());
+    assert_eq!(build_group(&ops), batches[0].groups[0]);
+    assert_eq!([GROUP_SIZE, 0, 0, 0, 0, 0, 0, 0], batches[0].op_counts);
+
+    // one more operation starts a new group
+    let ops = vec![Operation::Pad; GROUP_SIZE + 1];
+    let (batches, _) = super::batch_and_hash_ops(ops.clone());
+    assert_eq!(1, batches.len());
+    assert_eq!(2, batches[0].num_groups());
+    assert_eq!(build_group(&ops[..GROUP_SIZE]), batches[0].groups[0]);
+    assert_eq!(build_group(&ops[GROUP_SIZE..]), batches[0].groups[1]);
+    assert_eq!([GROUP_SIZE, 1, 0, 0, 0, 0, 0, 0], batches[0].op_counts);
+}
+
+#[test]
+fn batch_layout() {
+    // the first batch is filled with 7 PUSH operations and their immediate values, and the ops
+    // which don't fit into it spill into a second, partially filled batch
+    let mut ops = vec![Operation::Push(ONE); 7];
+    ops.extend([Operation::Add, Operation::Push(Felt::new(2)), Operation::Mul]);
+    let block = BasicBlockNode::new(ops, None).unwrap();
+
+    let layout = block.batch_layout();
+    assert_eq!(2, layout.len());
+    assert_eq!(
+        BatchLayout {
+            num_groups: 8,
+            imm_groups: vec![1, 2, 3, 4, 5, 6, 7],
+            num_ops: 8,
+        },
+        layout[0]
+    );
+    assert_eq!(
+        BatchLayout {
+            num_groups: 2,
+            imm_groups: vec![1],
+            num_ops: 2,
+        },
+        layout[1]
+    );
+}
+
 #[test]
 fn operation_or_decorator_iterator() {
     let mut mast_forest = MastForest::new();
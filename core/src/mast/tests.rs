@@ -1,11 +1,15 @@
-use alloc::vec::Vec;
+use alloc::{sync::Arc, vec::Vec};
 
 use miden_crypto::{hash::rpo::RpoDigest, Felt};
 use proptest::prelude::*;
 use rand_utils::prng_array;
 use winter_utils::{Deserializable, Serializable};
 
-use crate::{chiplets::hasher, mast::DynNode, Kernel, ProgramInfo, Word};
+use crate::{
+    chiplets::hasher,
+    mast::{DynNode, MastForest},
+    Kernel, Operation, Program, ProgramInfo, Word,
+};
 
 #[test]
 fn dyn_hash_is_correct() {
@@ -14,6 +18,35 @@ fn dyn_hash_is_correct() {
     assert_eq!(expected_constant, DynNode::new_dyn().digest());
 }
 
+#[test]
+fn program_contains_loops_and_calls() {
+    let mut mast_forest = MastForest::new();
+    let block_id = mast_forest.add_block(vec![Operation::Add], None).unwrap();
+    let split_id = mast_forest.add_split(block_id, block_id).unwrap();
+    let loop_id = mast_forest.add_loop(block_id).unwrap();
+    let call_id = mast_forest.add_call(loop_id).unwrap();
+    let dyncall_id = mast_forest.add_dyncall().unwrap();
+    mast_forest.make_root(split_id);
+    mast_forest.make_root(call_id);
+    mast_forest.make_root(dyncall_id);
+    let mast_forest = Arc::new(mast_forest);
+
+    // a program without loops or calls
+    let program = Program::new(mast_forest.clone(), split_id);
+    assert!(!program.contains_loops());
+    assert!(!program.contains_calls());
+
+    // a loop is found inside the body of a called procedure
+    let program = Program::new(mast_forest.clone(), call_id);
+    assert!(program.contains_loops());
+    assert!(program.contains_calls());
+
+    // a dynamic call is a call, but its callee is not known
+    let program = Program::new(mast_forest, dyncall_id);
+    assert!(!program.contains_loops());
+    assert!(program.contains_calls());
+}
+
 proptest! {
     #[test]
     fn arbitrary_program_info_serialization_works(
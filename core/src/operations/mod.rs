@@ -5,6 +5,10 @@ mod decorators;
 pub use decorators::{
     AssemblyOp, DebugOptions, Decorator, DecoratorIterator, DecoratorList, SignatureKind,
 };
+
+#[cfg(test)]
+mod tests;
+
 // OPERATIONS OP CODES
 // ================================================================================================
 use opcode_constants::*;
@@ -583,6 +587,103 @@ impl Operation {
         unsafe { *<*const _>::from(self).cast::<u8>() }
     }
 
+    /// Returns the user operation with the specified opcode, or None if the opcode does not
+    /// correspond to a user operation.
+    ///
+    /// This is the inverse of [Operation::op_code()] for user operations which do not carry any
+    /// associated data. None is returned for control flow operations (e.g., JOIN, SPAN, END), as
+    /// well as for operations whose associated data cannot be recovered from the opcode alone
+    /// (i.e., ASSERT, U32ASSERT2, MPVERIFY, PUSH, and EMIT).
+    pub fn from_op_code(op_code: u8) -> Option<Self> {
+        let operation = match op_code {
+            OPCODE_NOOP => Self::Noop,
+            OPCODE_EQZ => Self::Eqz,
+            OPCODE_NEG => Self::Neg,
+            OPCODE_INV => Self::Inv,
+            OPCODE_INCR => Self::Incr,
+            OPCODE_NOT => Self::Not,
+            OPCODE_FMPADD => Self::FmpAdd,
+            OPCODE_MLOAD => Self::MLoad,
+            OPCODE_SWAP => Self::Swap,
+            OPCODE_CALLER => Self::Caller,
+            OPCODE_MOVUP2 => Self::MovUp2,
+            OPCODE_MOVDN2 => Self::MovDn2,
+            OPCODE_MOVUP3 => Self::MovUp3,
+            OPCODE_MOVDN3 => Self::MovDn3,
+            OPCODE_ADVPOPW => Self::AdvPopW,
+            OPCODE_EXPACC => Self::Expacc,
+
+            OPCODE_MOVUP4 => Self::MovUp4,
+            OPCODE_MOVDN4 => Self::MovDn4,
+            OPCODE_MOVUP5 => Self::MovUp5,
+            OPCODE_MOVDN5 => Self::MovDn5,
+            OPCODE_MOVUP6 => Self::MovUp6,
+            OPCODE_MOVDN6 => Self::MovDn6,
+            OPCODE_MOVUP7 => Self::MovUp7,
+            OPCODE_MOVDN7 => Self::MovDn7,
+            OPCODE_SWAPW => Self::SwapW,
+            OPCODE_EXT2MUL => Self::Ext2Mul,
+            OPCODE_MOVUP8 => Self::MovUp8,
+            OPCODE_MOVDN8 => Self::MovDn8,
+            OPCODE_SWAPW2 => Self::SwapW2,
+            OPCODE_SWAPW3 => Self::SwapW3,
+            OPCODE_SWAPDW => Self::SwapDW,
+
+            OPCODE_EQ => Self::Eq,
+            OPCODE_ADD => Self::Add,
+            OPCODE_MUL => Self::Mul,
+            OPCODE_AND => Self::And,
+            OPCODE_OR => Self::Or,
+            OPCODE_U32AND => Self::U32and,
+            OPCODE_U32XOR => Self::U32xor,
+            OPCODE_FRIE2F4 => Self::FriE2F4,
+            OPCODE_DROP => Self::Drop,
+            OPCODE_CSWAP => Self::CSwap,
+            OPCODE_CSWAPW => Self::CSwapW,
+            OPCODE_MLOADW => Self::MLoadW,
+            OPCODE_MSTORE => Self::MStore,
+            OPCODE_MSTOREW => Self::MStoreW,
+            OPCODE_FMPUPDATE => Self::FmpUpdate,
+
+            OPCODE_PAD => Self::Pad,
+            OPCODE_DUP0 => Self::Dup0,
+            OPCODE_DUP1 => Self::Dup1,
+            OPCODE_DUP2 => Self::Dup2,
+            OPCODE_DUP3 => Self::Dup3,
+            OPCODE_DUP4 => Self::Dup4,
+            OPCODE_DUP5 => Self::Dup5,
+            OPCODE_DUP6 => Self::Dup6,
+            OPCODE_DUP7 => Self::Dup7,
+            OPCODE_DUP9 => Self::Dup9,
+            OPCODE_DUP11 => Self::Dup11,
+            OPCODE_DUP13 => Self::Dup13,
+            OPCODE_DUP15 => Self::Dup15,
+            OPCODE_ADVPOP => Self::AdvPop,
+            OPCODE_SDEPTH => Self::SDepth,
+            OPCODE_CLK => Self::Clk,
+
+            OPCODE_U32ADD => Self::U32add,
+            OPCODE_U32SUB => Self::U32sub,
+            OPCODE_U32MUL => Self::U32mul,
+            OPCODE_U32DIV => Self::U32div,
+            OPCODE_U32SPLIT => Self::U32split,
+
+            OPCODE_U32ADD3 => Self::U32add3,
+            OPCODE_U32MADD => Self::U32madd,
+
+            OPCODE_HPERM => Self::HPerm,
+
+            OPCODE_PIPE => Self::Pipe,
+            OPCODE_MSTREAM => Self::MStream,
+            OPCODE_RCOMBBASE => Self::RCombBase,
+
+            OPCODE_MRUPDATE => Self::MrUpdate,
+            _ => return None,
+        };
+
+        Some(operation)
+    }
+
     /// Returns an immediate value carried by this operation.
     pub fn imm_value(&self) -> Option<Felt> {
         match *self {
@@ -592,6 +693,116 @@ impl Operation {
         }
     }
 
+    /// Returns the net change in the depth of the operand stack caused by executing this
+    /// operation (i.e., the number of pushed elements minus the number of popped elements).
+    ///
+    /// The effect of END depends on the block being ended: when END exits a LOOP block whose body
+    /// was executed at least once, the loop condition is also popped off the stack. This is not
+    /// known from the operation alone, and thus, 0 is returned for END.
+    ///
+    /// CALL, SYSCALL, and DYNCALL start a new execution context; the returned value describes the
+    /// effect on the stack of the caller.
+    pub fn stack_effect(&self) -> i32 {
+        match self {
+            // ----- operations which push an element onto the stack ------------------------------
+            Self::SDepth
+            | Self::Clk
+            | Self::U32split
+            | Self::Pad
+            | Self::Dup0
+            | Self::Dup1
+            | Self::Dup2
+            | Self::Dup3
+            | Self::Dup4
+            | Self::Dup5
+            | Self::Dup6
+            | Self::Dup7
+            | Self::Dup9
+            | Self::Dup11
+            | Self::Dup13
+            | Self::Dup15
+            | Self::Push(_)
+            | Self::AdvPop => 1,
+
+            // ----- operations which pop an element off the stack --------------------------------
+            Self::Assert(_)
+            | Self::FmpUpdate
+            | Self::Split
+            | Self::Loop
+            | Self::Dyn
+            | Self::Dyncall
+            | Self::Repeat
+            | Self::Add
+            | Self::Mul
+            | Self::And
+            | Self::Or
+            | Self::Eq
+            | Self::U32add3
+            | Self::U32madd
+            | Self::U32and
+            | Self::U32xor
+            | Self::Drop
+            | Self::CSwap
+            | Self::CSwapW
+            | Self::MLoadW
+            | Self::MStoreW
+            | Self::MStore
+            | Self::FriE2F4 => -1,
+
+            // ----- operations which do not change the depth of the stack ------------------------
+            Self::Noop
+            | Self::FmpAdd
+            | Self::Caller
+            | Self::Emit(_)
+            | Self::Join
+            | Self::Call
+            | Self::SysCall
+            | Self::Span
+            | Self::End
+            | Self::Respan
+            | Self::Halt
+            | Self::Neg
+            | Self::Inv
+            | Self::Incr
+            | Self::Not
+            | Self::Eqz
+            | Self::Expacc
+            | Self::Ext2Mul
+            | Self::U32add
+            | Self::U32assert2(_)
+            | Self::U32sub
+            | Self::U32mul
+            | Self::U32div
+            | Self::Swap
+            | Self::SwapW
+            | Self::SwapW2
+            | Self::SwapW3
+            | Self::SwapDW
+            | Self::MovUp2
+            | Self::MovUp3
+            | Self::MovUp4
+            | Self::MovUp5
+            | Self::MovUp6
+            | Self::MovUp7
+            | Self::MovUp8
+            | Self::MovDn2
+            | Self::MovDn3
+            | Self::MovDn4
+            | Self::MovDn5
+            | Self::MovDn6
+            | Self::MovDn7
+            | Self::MovDn8
+            | Self::AdvPopW
+            | Self::MLoad
+            | Self::MStream
+            | Self::Pipe
+            | Self::HPerm
+            | Self::MpVerify(_)
+            | Self::MrUpdate
+            | Self::RCombBase => 0,
+        }
+    }
+
     /// Returns true if this operation writes any data to the decoder hasher registers.
     ///
     /// In other words, if so, then the user op helper registers are not available.
@@ -860,106 +1071,29 @@ impl Deserializable for Operation {
         let op_code = source.read_u8()?;
 
         let operation = match op_code {
-            OPCODE_NOOP => Self::Noop,
-            OPCODE_EQZ => Self::Eqz,
-            OPCODE_NEG => Self::Neg,
-            OPCODE_INV => Self::Inv,
-            OPCODE_INCR => Self::Incr,
-            OPCODE_NOT => Self::Not,
-            OPCODE_FMPADD => Self::FmpAdd,
-            OPCODE_MLOAD => Self::MLoad,
-            OPCODE_SWAP => Self::Swap,
-            OPCODE_CALLER => Self::Caller,
-            OPCODE_MOVUP2 => Self::MovUp2,
-            OPCODE_MOVDN2 => Self::MovDn2,
-            OPCODE_MOVUP3 => Self::MovUp3,
-            OPCODE_MOVDN3 => Self::MovDn3,
-            OPCODE_ADVPOPW => Self::AdvPopW,
-            OPCODE_EXPACC => Self::Expacc,
-
-            OPCODE_MOVUP4 => Self::MovUp4,
-            OPCODE_MOVDN4 => Self::MovDn4,
-            OPCODE_MOVUP5 => Self::MovUp5,
-            OPCODE_MOVDN5 => Self::MovDn5,
-            OPCODE_MOVUP6 => Self::MovUp6,
-            OPCODE_MOVDN6 => Self::MovDn6,
-            OPCODE_MOVUP7 => Self::MovUp7,
-            OPCODE_MOVDN7 => Self::MovDn7,
-            OPCODE_SWAPW => Self::SwapW,
-            OPCODE_EXT2MUL => Self::Ext2Mul,
-            OPCODE_MOVUP8 => Self::MovUp8,
-            OPCODE_MOVDN8 => Self::MovDn8,
-            OPCODE_SWAPW2 => Self::SwapW2,
-            OPCODE_SWAPW3 => Self::SwapW3,
-            OPCODE_SWAPDW => Self::SwapDW,
-
             OPCODE_ASSERT => {
                 let err_code = source.read_u32()?;
                 Self::Assert(err_code)
             },
-            OPCODE_EQ => Self::Eq,
-            OPCODE_ADD => Self::Add,
-            OPCODE_MUL => Self::Mul,
-            OPCODE_AND => Self::And,
-            OPCODE_OR => Self::Or,
-            OPCODE_U32AND => Self::U32and,
-            OPCODE_U32XOR => Self::U32xor,
-            OPCODE_FRIE2F4 => Self::FriE2F4,
-            OPCODE_DROP => Self::Drop,
-            OPCODE_CSWAP => Self::CSwap,
-            OPCODE_CSWAPW => Self::CSwapW,
-            OPCODE_MLOADW => Self::MLoadW,
-            OPCODE_MSTORE => Self::MStore,
-            OPCODE_MSTOREW => Self::MStoreW,
-            OPCODE_FMPUPDATE => Self::FmpUpdate,
 
-            OPCODE_PAD => Self::Pad,
-            OPCODE_DUP0 => Self::Dup0,
-            OPCODE_DUP1 => Self::Dup1,
-            OPCODE_DUP2 => Self::Dup2,
-            OPCODE_DUP3 => Self::Dup3,
-            OPCODE_DUP4 => Self::Dup4,
-            OPCODE_DUP5 => Self::Dup5,
-            OPCODE_DUP6 => Self::Dup6,
-            OPCODE_DUP7 => Self::Dup7,
-            OPCODE_DUP9 => Self::Dup9,
-            OPCODE_DUP11 => Self::Dup11,
-            OPCODE_DUP13 => Self::Dup13,
-            OPCODE_DUP15 => Self::Dup15,
-            OPCODE_ADVPOP => Self::AdvPop,
-            OPCODE_SDEPTH => Self::SDepth,
-            OPCODE_CLK => Self::Clk,
-
-            OPCODE_U32ADD => Self::U32add,
-            OPCODE_U32SUB => Self::U32sub,
-            OPCODE_U32MUL => Self::U32mul,
-            OPCODE_U32DIV => Self::U32div,
-            OPCODE_U32SPLIT => Self::U32split,
             OPCODE_U32ASSERT2 => {
                 let err_code = source.read_u32()?;
 
                 Self::U32assert2(err_code)
             },
-            OPCODE_U32ADD3 => Self::U32add3,
-            OPCODE_U32MADD => Self::U32madd,
 
-            OPCODE_HPERM => Self::HPerm,
             OPCODE_MPVERIFY => {
                 let err_code = source.read_u32()?;
 
                 Self::MpVerify(err_code)
             },
-            OPCODE_PIPE => Self::Pipe,
-            OPCODE_MSTREAM => Self::MStream,
             OPCODE_SPLIT => Self::Split,
             OPCODE_LOOP => Self::Loop,
             OPCODE_SPAN => Self::Span,
             OPCODE_JOIN => Self::Join,
             OPCODE_DYN => Self::Dyn,
             OPCODE_DYNCALL => Self::Dyncall,
-            OPCODE_RCOMBBASE => Self::RCombBase,
 
-            OPCODE_MRUPDATE => Self::MrUpdate,
             OPCODE_PUSH => {
                 let value_u64 = source.read_u64()?;
                 let value_felt = Felt::try_from(value_u64).map_err(|_| {
@@ -981,11 +1115,9 @@ impl Deserializable for Operation {
             OPCODE_REPEAT => Self::Repeat,
             OPCODE_RESPAN => Self::Respan,
             OPCODE_HALT => Self::Halt,
-            _ => {
-                return Err(DeserializationError::InvalidValue(format!(
-                    "Invalid opcode '{op_code}'"
-                )));
-            },
+            _ => Self::from_op_code(op_code).ok_or_else(|| {
+                DeserializationError::InvalidValue(format!("Invalid opcode '{op_code}'"))
+            })?,
         };
 
         Ok(operation)
@@ -0,0 +1,78 @@
+use super::Operation;
+use crate::{
+    utils::{Deserializable, Serializable},
+    ONE,
+};
+
+// OPCODE MAPPING TESTS
+// ================================================================================================
+
+#[test]
+fn from_op_code_round_trip() {
+    let mut num_user_ops = 0;
+    for op_code in 0..=u8::MAX {
+        let Some(op) = Operation::from_op_code(op_code) else {
+            continue;
+        };
+        num_user_ops += 1;
+
+        // the mapping must be the inverse of op_code()
+        assert_eq!(op_code, op.op_code());
+
+        // and must agree with deserialization of operations without associated data
+        let bytes = op.to_bytes();
+        assert_eq!(op, Operation::read_from_bytes(&bytes).unwrap());
+    }
+
+    assert_eq!(74, num_user_ops);
+}
+
+#[test]
+fn from_op_code_control_flow_and_immediates() {
+    let ops = [
+        Operation::Join,
+        Operation::Split,
+        Operation::Loop,
+        Operation::Call,
+        Operation::Dyn,
+        Operation::Dyncall,
+        Operation::SysCall,
+        Operation::Span,
+        Operation::End,
+        Operation::Repeat,
+        Operation::Respan,
+        Operation::Halt,
+        Operation::Assert(1),
+        Operation::U32assert2(1),
+        Operation::MpVerify(1),
+        Operation::Push(ONE),
+        Operation::Emit(1),
+    ];
+
+    for op in ops {
+        assert_eq!(None, Operation::from_op_code(op.op_code()), "{op}");
+    }
+}
+
+// STACK EFFECT TESTS
+// ================================================================================================
+
+#[test]
+fn stack_effect() {
+    assert_eq!(-1, Operation::Drop.stack_effect());
+    assert_eq!(1, Operation::Dup0.stack_effect());
+    assert_eq!(-1, Operation::Add.stack_effect());
+    assert_eq!(0, Operation::Swap.stack_effect());
+    assert_eq!(1, Operation::Push(ONE).stack_effect());
+    assert_eq!(-1, Operation::Assert(0).stack_effect());
+    assert_eq!(1, Operation::U32split.stack_effect());
+    assert_eq!(-1, Operation::U32madd.stack_effect());
+    assert_eq!(-1, Operation::MStoreW.stack_effect());
+    assert_eq!(0, Operation::HPerm.stack_effect());
+
+    // control flow operations which consume a condition or a memory address
+    assert_eq!(-1, Operation::Split.stack_effect());
+    assert_eq!(-1, Operation::Dyn.stack_effect());
+    assert_eq!(0, Operation::Join.stack_effect());
+    assert_eq!(0, Operation::End.stack_effect());
+}
@@ -1,4 +1,4 @@
-use alloc::{sync::Arc, vec::Vec};
+use alloc::{collections::BTreeSet, sync::Arc, vec::Vec};
 use core::fmt;
 
 use miden_crypto::{hash::rpo::RpoDigest, Felt, WORD_SIZE};
@@ -99,6 +99,62 @@ impl Program {
     pub fn num_procedures(&self) -> u32 {
         self.mast_forest.num_procedures()
     }
+
+    /// Returns true if any node reachable from the program's entrypoint is a LOOP node.
+    ///
+    /// The bodies of called procedures are included, but procedures referenced via external nodes
+    /// or invoked dynamically (i.e., via DYN or DYNCALL) are not, since they are not known before
+    /// execution.
+    pub fn contains_loops(&self) -> bool {
+        self.any_reachable_node(|node| matches!(node, MastNode::Loop(_)))
+    }
+
+    /// Returns true if any node reachable from the program's entrypoint starts a new execution
+    /// context, i.e., is a CALL, SYSCALL, or DYNCALL node.
+    ///
+    /// As with [Self::contains_loops()], procedures referenced via external nodes are not
+    /// searched.
+    pub fn contains_calls(&self) -> bool {
+        self.any_reachable_node(|node| match node {
+            MastNode::Call(_) => true,
+            MastNode::Dyn(node) => node.is_dyncall(),
+            _ => false,
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+/// Helpers
+impl Program {
+    /// Returns true if the predicate holds for any node reachable from the program's entrypoint.
+    ///
+    /// The MAST is traversed iteratively so that deeply nested programs cannot overflow the call
+    /// stack, and nodes shared by several parents are visited only once.
+    fn any_reachable_node(&self, predicate: impl Fn(&MastNode) -> bool) -> bool {
+        let mut visited = BTreeSet::new();
+        let mut to_visit = vec![self.entrypoint];
+
+        while let Some(node_id) = to_visit.pop() {
+            if !visited.insert(node_id) {
+                continue;
+            }
+
+            let node = &self.mast_forest[node_id];
+            if predicate(node) {
+                return true;
+            }
+
+            match node {
+                MastNode::Join(node) => to_visit.extend([node.second(), node.first()]),
+                MastNode::Split(node) => to_visit.extend([node.on_false(), node.on_true()]),
+                MastNode::Loop(node) => to_visit.push(node.body()),
+                MastNode::Call(node) => to_visit.push(node.callee()),
+                MastNode::Block(_) | MastNode::Dyn(_) | MastNode::External(_) => (),
+            }
+        }
+
+        false
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -1,18 +1,33 @@
 use alloc::vec::Vec;
+use core::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
 
-use super::{Felt, Word, ONE, ZERO};
+use super::{DecoderError, Felt, Word, ONE, ZERO};
 use crate::system::ContextId;
 
 // BLOCK STACK
 // ================================================================================================
 
 /// Keeps track of code blocks which are currently being executed by the VM.
-#[derive(Default)]
+///
+/// The number of blocks which can be on the stack at the same time (i.e., the block nesting depth)
+/// is limited by `max_depth`.
 pub struct BlockStack {
     blocks: Vec<BlockInfo>,
+    max_depth: usize,
 }
 
 impl BlockStack {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns an empty [BlockStack] which can hold at most `max_depth` blocks.
+    pub fn new(max_depth: usize) -> Self {
+        Self { blocks: Vec::new(), max_depth }
+    }
+
     // STATE ACCESSORS AND MUTATORS
     // --------------------------------------------------------------------------------------------
 
@@ -22,12 +37,20 @@ impl BlockStack {
     /// is. Additionally, for CALL, SYSCALL and DYNCALL blocks, execution context info must be
     /// provided. Other information (i.e., the block's parent, whether the block is a body of a loop
     /// or a first child of a JOIN block) is determined from the information already on the stack.
+    ///
+    /// # Errors
+    /// Returns an error if pushing the block would make the number of blocks on the stack exceed
+    /// the maximum nesting depth.
     pub fn push(
         &mut self,
         addr: Felt,
         block_type: BlockType,
         ctx_info: Option<ExecutionContextInfo>,
-    ) -> Felt {
+    ) -> Result<Felt, DecoderError> {
+        if self.blocks.len() >= self.max_depth {
+            return Err(DecoderError::MaxNestingExceeded(self.max_depth));
+        }
+
         // make sure execution context was provided for CALL, SYSCALL and DYNCALL blocks
         if block_type == BlockType::Call
             || block_type == BlockType::SysCall
@@ -65,13 +88,17 @@ impl BlockStack {
             ctx_info,
             is_loop_body,
             is_first_child,
+            num_repeats: 0,
         });
-        parent_addr
+        Ok(parent_addr)
     }
 
     /// Removes a block from the top of the stack and returns it.
-    pub fn pop(&mut self) -> BlockInfo {
-        let block = self.blocks.pop().expect("block stack is empty");
+    ///
+    /// # Errors
+    /// Returns an error if the stack is empty.
+    pub fn pop(&mut self) -> Result<BlockInfo, DecoderError> {
+        let block = self.blocks.pop().ok_or(DecoderError::EmptyBlockStack)?;
         // if the parent block is a JOIN block (i.e., we just finished executing a child of a JOIN
         // block) and if the first_child_executed hasn't been set to true yet, set it to true
         if let Some(parent) = self.blocks.last_mut() {
@@ -81,17 +108,41 @@ impl BlockStack {
                 }
             }
         }
-        block
+        Ok(block)
+    }
+
+    /// Returns the number of blocks currently on the stack.
+    pub fn depth(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Returns the number of blocks on the stack which started a new execution context.
+    ///
+    /// Only CALL, SYSCALL, and DYNCALL blocks carry execution context info, and thus, this is the
+    /// depth of nested calls at which the VM is currently executing.
+    pub fn call_depth(&self) -> usize {
+        self.blocks.iter().filter(|block| block.ctx_info.is_some()).count()
+    }
+
+    /// Returns the number of bytes currently allocated for the blocks on this stack.
+    pub fn estimate_memory(&self) -> usize {
+        self.blocks.capacity() * size_of::<BlockInfo>()
     }
 
     /// Returns a reference to a block at the top of the stack.
-    pub fn peek(&self) -> &BlockInfo {
-        self.blocks.last().expect("block stack is empty")
+    ///
+    /// # Errors
+    /// Returns an error if the stack is empty.
+    pub fn peek(&self) -> Result<&BlockInfo, DecoderError> {
+        self.blocks.last().ok_or(DecoderError::EmptyBlockStack)
     }
 
     /// Returns a mutable reference to a block at the top of the stack.
-    pub fn peek_mut(&mut self) -> &mut BlockInfo {
-        self.blocks.last_mut().expect("block stack is empty")
+    ///
+    /// # Errors
+    /// Returns an error if the stack is empty.
+    pub fn peek_mut(&mut self) -> Result<&mut BlockInfo, DecoderError> {
+        self.blocks.last_mut().ok_or(DecoderError::EmptyBlockStack)
     }
 }
 
@@ -99,6 +150,9 @@ impl BlockStack {
 // ================================================================================================
 
 /// Contains basic information about a code block.
+///
+/// Two blocks are considered equal if they have the same address and parent address; blocks are
+/// ordered by address first, and then by parent address.
 #[derive(Debug)]
 pub struct BlockInfo {
     pub addr: Felt,
@@ -108,9 +162,16 @@ pub struct BlockInfo {
     pub is_loop_body: bool,
     #[allow(dead_code)] // TODO: remove this filed
     pub is_first_child: bool,
+    /// Number of times the body of this block was repeated; relevant only for LOOP blocks.
+    pub num_repeats: usize,
 }
 
 impl BlockInfo {
+    /// Returns the type of this block.
+    pub const fn block_type(&self) -> BlockType {
+        self.block_type
+    }
+
     /// Returns ONE if the this block is a LOOP block and the body of the loop was executed at
     /// least once; otherwise, returns ZERO.
     pub fn is_entered_loop(&self) -> Felt {
@@ -145,6 +206,40 @@ impl BlockInfo {
             _ => ZERO,
         }
     }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the key by which blocks are compared, ordered, and hashed.
+    fn key(&self) -> (u64, u64) {
+        (self.addr.as_int(), self.parent_addr.as_int())
+    }
+}
+
+impl PartialEq for BlockInfo {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for BlockInfo {}
+
+impl PartialOrd for BlockInfo {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BlockInfo {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl Hash for BlockInfo {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
 }
 
 // EXECUTION CONTEXT INFO
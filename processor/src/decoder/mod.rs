@@ -1,4 +1,7 @@
-use alloc::vec::Vec;
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::Instant;
 
 use miden_air::{
     trace::{
@@ -12,20 +15,25 @@ use miden_air::{
 };
 use vm_core::{
     mast::{
-        BasicBlockNode, CallNode, DynNode, JoinNode, LoopNode, MastForest, SplitNode, OP_BATCH_SIZE,
+        BasicBlockNode, CallNode, DynNode, JoinNode, LoopNode, MastForest, SplitNode,
+        OP_BATCH_SIZE, OP_GROUP_SIZE,
     },
     stack::MIN_STACK_DEPTH,
     AssemblyOp,
 };
 
 use super::{
-    ExecutionError, Felt, OpBatch, Operation, Process, Word, EMPTY_WORD, MIN_TRACE_LEN, ONE, ZERO,
+    ColMatrix, DecoderError, Digest, ExecutionError, ExecutionOptions, Felt, OpBatch, Operation,
+    Process, Word, EMPTY_WORD, MIN_TRACE_LEN, ONE, ZERO,
 };
 use crate::Host;
 
 mod trace;
 use trace::DecoderTrace;
 
+mod trace_analysis;
+pub use trace_analysis::{operation_from_op_code, RedundancyKind};
+
 mod aux_trace;
 pub use aux_trace::AuxTraceBuilder;
 #[cfg(test)]
@@ -36,6 +44,9 @@ use block_stack::{BlockStack, BlockType, ExecutionContextInfo};
 #[cfg(test)]
 use miden_air::trace::decoder::NUM_USER_OP_HELPERS;
 
+mod op_log;
+pub use op_log::{DecoderCall, DecoderOpLog};
+
 #[cfg(test)]
 mod tests;
 
@@ -81,7 +92,7 @@ impl Process {
 
         // start decoding the JOIN block; this appends a row with JOIN operation to the decoder
         // trace. when JOIN operation is executed, the rest of the VM state does not change
-        self.decoder.start_join(child1_hash, child2_hash, addr);
+        self.decoder.start_join(child1_hash, child2_hash, addr)?;
         self.execute_op(Operation::Noop, host)
     }
 
@@ -93,7 +104,7 @@ impl Process {
     ) -> Result<(), ExecutionError> {
         // this appends a row with END operation to the decoder trace. when END operation is
         // executed the rest of the VM state does not change
-        self.decoder.end_control_block(node.digest().into());
+        self.decoder.end_control_block(node.digest().into())?;
 
         self.execute_op(Operation::Noop, host)
     }
@@ -133,7 +144,7 @@ impl Process {
 
         // start decoding the SPLIT block. this appends a row with SPLIT operation to the decoder
         // trace. we also pop the value off the top of the stack and return it.
-        self.decoder.start_split(child1_hash, child2_hash, addr);
+        self.decoder.start_split(child1_hash, child2_hash, addr, condition)?;
         self.execute_op(Operation::Drop, host)?;
         Ok(condition)
     }
@@ -146,7 +157,7 @@ impl Process {
     ) -> Result<(), ExecutionError> {
         // this appends a row with END operation to the decoder trace. when END operation is
         // executed the rest of the VM state does not change
-        self.decoder.end_control_block(block.digest().into());
+        self.decoder.end_control_block(block.digest().into())?;
 
         self.execute_op(Operation::Noop, host)
     }
@@ -185,7 +196,7 @@ impl Process {
         // as the loop block, and the hash of the body will not be added to the block hash table.
         // basically, if the top of the stack is ZERO, a LOOP operation should be immediately
         // followed by an END operation.
-        self.decoder.start_loop(body_hash, addr, condition);
+        self.decoder.start_loop(body_hash, addr, condition)?;
         self.execute_op(Operation::Drop, host)?;
         Ok(condition)
     }
@@ -199,7 +210,7 @@ impl Process {
         host: &mut H,
     ) -> Result<(), ExecutionError> {
         // this appends a row with END operation to the decoder trace.
-        self.decoder.end_control_block(node.digest().into());
+        self.decoder.end_control_block(node.digest().into())?;
 
         // if we are exiting a loop, we also need to pop the top value off the stack (and this
         // value must be ZERO - otherwise, we should have stayed in the loop). but, if we never
@@ -257,10 +268,10 @@ impl Process {
 
         if node.is_syscall() {
             self.system.start_syscall();
-            self.decoder.start_syscall(callee_hash, addr, ctx_info);
+            self.decoder.start_syscall(callee_hash, addr, ctx_info)?;
         } else {
             self.system.start_call_or_dyncall(callee_hash);
-            self.decoder.start_call(callee_hash, addr, ctx_info);
+            self.decoder.start_call(callee_hash, addr, ctx_info)?;
         }
 
         // the rest of the VM state does not change
@@ -283,7 +294,7 @@ impl Process {
         // information about the execution context prior to execution of the CALL block
         let ctx_info = self
             .decoder
-            .end_control_block(node.digest().into())
+            .end_control_block(node.digest().into())?
             .expect("no execution context");
 
         // when returning from a function call or a syscall, restore the context of the system
@@ -331,7 +342,7 @@ impl Process {
             dyn_node.digest(),
         );
 
-        self.decoder.start_dyn(addr, callee_hash);
+        self.decoder.start_dyn(addr, callee_hash)?;
 
         // Pop the memory address off the stack.
         self.execute_op(Operation::Drop, host)?;
@@ -387,7 +398,7 @@ impl Process {
         );
 
         self.system.start_call_or_dyncall(callee_hash);
-        self.decoder.start_dyncall(addr, callee_hash, ctx_info);
+        self.decoder.start_dyncall(addr, callee_hash, ctx_info)?;
 
         self.advance_clock()?;
 
@@ -402,7 +413,7 @@ impl Process {
     ) -> Result<(), ExecutionError> {
         // this appends a row with END operation to the decoder trace. when the END operation is
         // executed the rest of the VM state does not change
-        self.decoder.end_control_block(dyn_node.digest().into());
+        self.decoder.end_control_block(dyn_node.digest().into())?;
 
         self.execute_op(Operation::Noop, host)
     }
@@ -423,7 +434,7 @@ impl Process {
         // executed the rest of the VM state does not change
         let ctx_info = self
             .decoder
-            .end_control_block(dyn_node.digest().into())
+            .end_control_block(dyn_node.digest().into())?
             .expect("no execution context");
 
         // when returning from a function call, restore the context of the system
@@ -462,7 +473,7 @@ impl Process {
         // set the value of the group_count register at the beginning of the SPAN.
         let num_op_groups = basic_block.num_op_groups();
         self.decoder
-            .start_basic_block(&op_batches[0], Felt::new(num_op_groups as u64), addr);
+            .start_basic_block(&op_batches[0], Felt::new(num_op_groups as u64), addr)?;
         self.execute_op(Operation::Noop, host)
     }
 
@@ -474,14 +485,15 @@ impl Process {
     ) -> Result<(), ExecutionError> {
         // this appends a row with END operation to the decoder trace. when END operation is
         // executed the rest of the VM state does not change
-        self.decoder.end_basic_block(block.digest().into());
+        self.decoder.end_basic_block(block.digest().into())?;
 
         self.execute_op(Operation::Noop, host)
     }
 
     /// Continues decoding a SPAN block by absorbing the next batch of operations.
-    pub(super) fn respan(&mut self, op_batch: &OpBatch) {
-        self.decoder.respan(op_batch);
+    pub(super) fn respan(&mut self, op_batch: &OpBatch) -> Result<(), ExecutionError> {
+        self.decoder.respan(op_batch)?;
+        Ok(())
     }
 }
 
@@ -533,11 +545,19 @@ impl Process {
 /// - An instance of [DebugInfo] which is only populated in debug mode. This debug_info instance
 ///   includes operations executed by the VM and AsmOp decorators. AsmOp decorators are populated
 ///   only when both the processor and assembler are in debug mode.
+///
+/// The decoder also limits how deeply code blocks can be nested within each other. By default, at
+/// most [ExecutionOptions::DEFAULT_MAX_BLOCK_NESTING_DEPTH] blocks can be executing at the same
+/// time; starting a block beyond this limit results in an error. The limit is set via
+/// [ExecutionOptions::with_max_block_nesting_depth()].
 pub struct Decoder {
     block_stack: BlockStack,
     span_context: Option<SpanContext>,
     trace: DecoderTrace,
     debug_info: DebugInfo,
+    op_log: Option<DecoderOpLog>,
+    max_loop_repeats: usize,
+    max_op_group_value: Felt,
 }
 
 impl Decoder {
@@ -546,13 +566,45 @@ impl Decoder {
     /// Returns an empty instance of [Decoder].
     pub fn new(in_debug_mode: bool) -> Self {
         Self {
-            block_stack: BlockStack::default(),
+            block_stack: BlockStack::new(ExecutionOptions::DEFAULT_MAX_BLOCK_NESTING_DEPTH),
             span_context: None,
             trace: DecoderTrace::new(),
             debug_info: DebugInfo::new(in_debug_mode),
+            op_log: None,
+            max_loop_repeats: usize::MAX,
+            max_op_group_value: ZERO,
         }
     }
 
+    /// Sets the maximum number of code blocks which can be nested within each other.
+    ///
+    /// This should be called before any blocks are decoded.
+    pub fn with_max_nesting_depth(mut self, max_depth: usize) -> Self {
+        debug_assert_eq!(0, self.block_stack.depth(), "decoding already started");
+        self.block_stack = BlockStack::new(max_depth);
+        self
+    }
+
+    /// Sets the maximum number of times the body of a single LOOP block can be repeated.
+    ///
+    /// The first execution of a loop body is not preceded by a REPEAT operation, and thus, is not
+    /// counted; a loop can be entered and then repeated at most `max_repeats` times. By default,
+    /// the number of repeats is not limited. When a program is executed by a [Process], the limit
+    /// is taken from [ExecutionOptions::max_loop_repeats()].
+    pub fn with_max_loop_repeats(mut self, max_repeats: usize) -> Self {
+        self.max_loop_repeats = max_repeats;
+        self
+    }
+
+    /// Enables recording of the calls made to this decoder into a [DecoderOpLog] if `enabled` is
+    /// true.
+    ///
+    /// Recording is disabled by default because the log grows with every decoded operation.
+    pub fn with_op_log(mut self, enabled: bool) -> Self {
+        self.op_log = enabled.then(DecoderOpLog::default);
+        self
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -569,6 +621,68 @@ impl Decoder {
         self.trace.program_hash()
     }
 
+    /// Returns the number of CALL, SYSCALL, and DYNCALL blocks which are currently being executed.
+    pub fn call_depth(&self) -> usize {
+        self.block_stack.call_depth()
+    }
+
+    /// Returns the largest value of an operation group decoded so far, or ZERO if no operation
+    /// groups have been decoded.
+    ///
+    /// Values are compared as integers. Groups holding immediate values are not operation groups,
+    /// and thus, are not considered.
+    pub fn max_op_group_value(&self) -> Felt {
+        self.max_op_group_value
+    }
+
+    /// Returns an estimate of the number of bytes currently held by the trace buffers and the
+    /// block stack of this decoder.
+    ///
+    /// The estimate does not account for padding added when the trace is finalized: at that point,
+    /// every column of the trace is extended to the full trace length.
+    pub fn estimate_memory(&self) -> usize {
+        self.trace.estimate_memory() + self.block_stack.estimate_memory()
+    }
+
+    /// Returns the number of rows which will be appended to the trace of this decoder when it is
+    /// extended to `trace_len` rows via [Decoder::into_trace()].
+    ///
+    /// The returned number includes the last `num_rand_rows` rows which are later overwritten with
+    /// random values.
+    ///
+    /// # Errors
+    /// Returns an error if the trace of this decoder together with `num_rand_rows` random rows
+    /// does not fit into `trace_len` rows.
+    pub fn padding_rows_for(
+        &self,
+        trace_len: usize,
+        num_rand_rows: usize,
+    ) -> Result<usize, DecoderError> {
+        let min_len = self.trace_len() + num_rand_rows;
+        if trace_len < min_len {
+            return Err(DecoderError::TraceLenTooSmall { trace_len, min_len });
+        }
+
+        Ok(trace_len - self.trace_len())
+    }
+
+    /// Returns the hash of the loop body recorded at the specified row of the trace, or None if
+    /// the operation executed at this row is not a LOOP operation.
+    ///
+    /// The hash is read from the first 4 registers of the hasher section of the decoder trace.
+    pub fn loop_body_hash(&self, row: usize) -> Option<Word> {
+        self.trace.loop_body_hash(row)
+    }
+
+    /// Returns the opcodes of the operations which are still left to be executed in the current
+    /// operation group, or None if the decoder is not currently decoding a SPAN block.
+    ///
+    /// The opcodes are packed into a single field element, with the next operation to be executed
+    /// located at the least significant position.
+    pub fn peek_group_ops_left(&self) -> Option<Felt> {
+        self.span_context.as_ref().map(|ctx| ctx.group_ops_left.into())
+    }
+
     pub fn debug_info(&self) -> &DebugInfo {
         debug_assert!(self.in_debug_mode());
         &self.debug_info
@@ -579,6 +693,51 @@ impl Decoder {
         self.debug_info.in_debug_mode()
     }
 
+    /// Returns a log of calls made to this decoder, or None if recording of calls has not been
+    /// enabled via [Decoder::with_op_log()].
+    ///
+    /// The log can be replayed via [Decoder::replay()].
+    pub fn op_log(&self) -> Option<&DecoderOpLog> {
+        self.op_log.as_ref()
+    }
+
+    /// Checks that decoding of a program has been completed.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Decoding of a SPAN block has been started but not completed.
+    /// - The block stack is not empty (i.e., some code blocks have been started but not ended).
+    pub fn validate_well_formed(&self) -> Result<(), DecoderError> {
+        if self.span_context.is_some() {
+            return Err(DecoderError::UnfinishedSpanBlock);
+        }
+
+        let num_open_blocks = self.block_stack.depth();
+        if num_open_blocks != 0 {
+            return Err(DecoderError::UnfinishedCodeBlocks(num_open_blocks));
+        }
+
+        Ok(())
+    }
+
+    /// Checks that decoding of a program has been completed, and that the decoded program has the
+    /// specified hash.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Decoding of the program has not been completed (see [Decoder::validate_well_formed()]).
+    /// - The hash of the decoded program is different from `program_hash`.
+    pub fn verify(&self, program_hash: Digest) -> Result<(), DecoderError> {
+        self.validate_well_formed()?;
+
+        let actual = Digest::new(self.program_hash());
+        if actual != program_hash {
+            return Err(DecoderError::ProgramHashMismatch { expected: program_hash, actual });
+        }
+
+        Ok(())
+    }
+
     // CONTROL BLOCKS
     // --------------------------------------------------------------------------------------------
 
@@ -586,78 +745,141 @@ impl Decoder {
     ///
     /// This pushes a block with ID=addr onto the block stack and appends execution of a JOIN
     /// operation to the trace.
-    pub fn start_join(&mut self, child1_hash: Word, child2_hash: Word, addr: Felt) {
+    pub fn start_join(
+        &mut self,
+        child1_hash: Word,
+        child2_hash: Word,
+        addr: Felt,
+    ) -> Result<(), DecoderError> {
         // append a JOIN row to the execution trace
-        let parent_addr = self.block_stack.push(addr, BlockType::Join(false), None);
+        let parent_addr = self.block_stack.push(addr, BlockType::Join(false), None)?;
         self.trace
             .append_block_start(parent_addr, Operation::Join, child1_hash, child2_hash);
 
         self.debug_info.append_operation(Operation::Join);
+        self.record_call(|| DecoderCall::StartJoin { child1_hash, child2_hash, addr });
+
+        Ok(())
     }
 
     /// Starts decoding of a SPLIT block.
     ///
     /// This pushes a block with ID=addr onto the block stack and appends execution of a SPLIT
-    /// operation to the trace.
-    pub fn start_split(&mut self, child1_hash: Word, child2_hash: Word, addr: Felt) {
+    /// operation to the trace. The true branch of the block is taken only if stack_top = ONE.
+    pub fn start_split(
+        &mut self,
+        child1_hash: Word,
+        child2_hash: Word,
+        addr: Felt,
+        stack_top: Felt,
+    ) -> Result<(), DecoderError> {
         // append a SPLIT row to the execution trace
-        let parent_addr = self.block_stack.push(addr, BlockType::Split, None);
+        let parent_addr = self.block_stack.push(addr, BlockType::Split, None)?;
         self.trace
             .append_block_start(parent_addr, Operation::Split, child1_hash, child2_hash);
 
         self.debug_info.append_operation(Operation::Split);
+        // a non-binary condition causes execution to fail before either branch is taken, and
+        // thus, only binary conditions are recorded
+        if stack_top == ONE || stack_top == ZERO {
+            self.debug_info.append_split_branch(addr, stack_top == ONE);
+        }
+        self.record_call(|| DecoderCall::StartSplit {
+            child1_hash,
+            child2_hash,
+            addr,
+            stack_top,
+        });
+
+        Ok(())
     }
 
     /// Starts decoding of a LOOP block.
     ///
     /// This pushes a block with ID=addr onto the block stack and appends execution of a LOOP
     /// operation to the trace. A block is marked as a loop block only if is_loop = ONE.
-    pub fn start_loop(&mut self, loop_body_hash: Word, addr: Felt, stack_top: Felt) {
+    pub fn start_loop(
+        &mut self,
+        loop_body_hash: Word,
+        addr: Felt,
+        stack_top: Felt,
+    ) -> Result<(), DecoderError> {
         // append a LOOP row to the execution trace
         let enter_loop = stack_top == ONE;
-        let parent_addr = self.block_stack.push(addr, BlockType::Loop(enter_loop), None);
+        let parent_addr = self.block_stack.push(addr, BlockType::Loop(enter_loop), None)?;
         self.trace
             .append_block_start(parent_addr, Operation::Loop, loop_body_hash, EMPTY_WORD);
 
         self.debug_info.append_operation(Operation::Loop);
+        self.record_call(|| DecoderCall::StartLoop { loop_body_hash, addr, stack_top });
+
+        Ok(())
     }
 
     /// Starts decoding another iteration of a loop.
     ///
     /// This appends an execution of a REPEAT operation to the trace.
-    pub fn repeat(&mut self) {
-        // append a REPEAT row to the execution trace
-        let block_info = self.block_stack.peek();
+    ///
+    /// # Errors
+    /// Returns an error if the body of the loop has already been repeated the maximum number of
+    /// times.
+    pub fn repeat(&mut self) -> Result<(), DecoderError> {
+        let block_info = self.block_stack.peek_mut()?;
         debug_assert_eq!(ONE, block_info.is_entered_loop());
+        if block_info.num_repeats == self.max_loop_repeats {
+            return Err(DecoderError::LoopIterationLimitExceeded(self.max_loop_repeats));
+        }
+        block_info.num_repeats += 1;
+
+        // append a REPEAT row to the execution trace
         self.trace.append_loop_repeat(block_info.addr);
 
         self.debug_info.append_operation(Operation::Repeat);
+        self.record_call(|| DecoderCall::Repeat);
+
+        Ok(())
     }
 
     /// Starts decoding of a CALL block.
     ///
     /// This pushes a block with ID=addr onto the block stack and appends execution of a CALL
     /// operation to the trace.
-    pub fn start_call(&mut self, fn_hash: Word, addr: Felt, ctx_info: ExecutionContextInfo) {
+    pub fn start_call(
+        &mut self,
+        fn_hash: Word,
+        addr: Felt,
+        ctx_info: ExecutionContextInfo,
+    ) -> Result<(), DecoderError> {
         // push CALL block info onto the block stack and append a CALL row to the execution trace
-        let parent_addr = self.block_stack.push(addr, BlockType::Call, Some(ctx_info));
+        let parent_addr = self.block_stack.push(addr, BlockType::Call, Some(ctx_info))?;
         self.trace.append_block_start(parent_addr, Operation::Call, fn_hash, EMPTY_WORD);
 
         self.debug_info.append_operation(Operation::Call);
+        self.record_call(|| DecoderCall::StartCall { fn_hash, addr, ctx_info });
+
+        Ok(())
     }
 
     /// Starts decoding of a SYSCALL block.
     ///
     /// This pushes a block with ID=addr onto the block stack and appends execution of a SYSCALL
     /// operation to the trace.
-    pub fn start_syscall(&mut self, fn_hash: Word, addr: Felt, ctx_info: ExecutionContextInfo) {
+    pub fn start_syscall(
+        &mut self,
+        fn_hash: Word,
+        addr: Felt,
+        ctx_info: ExecutionContextInfo,
+    ) -> Result<(), DecoderError> {
         // push SYSCALL block info onto the block stack and append a SYSCALL row to the execution
         // trace
-        let parent_addr = self.block_stack.push(addr, BlockType::SysCall, Some(ctx_info));
+        let parent_addr = self.block_stack.push(addr, BlockType::SysCall, Some(ctx_info))?;
         self.trace
             .append_block_start(parent_addr, Operation::SysCall, fn_hash, EMPTY_WORD);
 
         self.debug_info.append_operation(Operation::SysCall);
+        self.record_call(|| DecoderCall::StartSyscall { fn_hash, addr, ctx_info });
+
+        Ok(())
     }
 
     /// Starts decoding of a DYN block.
@@ -671,13 +893,16 @@ impl Decoder {
     ///
     /// This pushes a block with ID=addr onto the block stack and appends execution of a DYN
     /// operation to the trace.
-    pub fn start_dyn(&mut self, addr: Felt, callee_hash: Word) {
+    pub fn start_dyn(&mut self, addr: Felt, callee_hash: Word) -> Result<(), DecoderError> {
         // push DYN block info onto the block stack and append a DYN row to the execution trace
-        let parent_addr = self.block_stack.push(addr, BlockType::Dyn, None);
+        let parent_addr = self.block_stack.push(addr, BlockType::Dyn, None)?;
         self.trace
             .append_block_start(parent_addr, Operation::Dyn, callee_hash, [ZERO; 4]);
 
         self.debug_info.append_operation(Operation::Dyn);
+        self.record_call(|| DecoderCall::StartDyn { addr, callee_hash });
+
+        Ok(())
     }
 
     /// Starts decoding of a DYNCALL block.
@@ -691,11 +916,16 @@ impl Decoder {
     /// left). We need to store those in the decoder trace so that the block stack table can access
     /// them (since in the next row, we start a new context, and hence the stack registers are reset
     /// to their default values).
-    pub fn start_dyncall(&mut self, addr: Felt, callee_hash: Word, ctx_info: ExecutionContextInfo) {
+    pub fn start_dyncall(
+        &mut self,
+        addr: Felt,
+        callee_hash: Word,
+        ctx_info: ExecutionContextInfo,
+    ) -> Result<(), DecoderError> {
         let parent_stack_depth = ctx_info.parent_stack_depth.into();
         let parent_next_overflow_addr = ctx_info.parent_next_overflow_addr;
 
-        let parent_addr = self.block_stack.push(addr, BlockType::Dyncall, Some(ctx_info));
+        let parent_addr = self.block_stack.push(addr, BlockType::Dyncall, Some(ctx_info))?;
         self.trace.append_block_start(
             parent_addr,
             Operation::Dyncall,
@@ -704,6 +934,9 @@ impl Decoder {
         );
 
         self.debug_info.append_operation(Operation::Dyncall);
+        self.record_call(|| DecoderCall::StartDyncall { addr, callee_hash, ctx_info });
+
+        Ok(())
     }
 
     /// Ends decoding of a control block (i.e., a non-SPAN block).
@@ -714,9 +947,16 @@ impl Decoder {
     /// If the ended block is a CALL or a SYSCALL block, this method will return values to which
     /// execution context and free memory pointers were set before the CALL block started
     /// executing. For non-CALL blocks these values are set to zeros and should be ignored.
-    pub fn end_control_block(&mut self, block_hash: Word) -> Option<ExecutionContextInfo> {
+    ///
+    /// # Errors
+    /// Returns an error if the block stack is empty.
+    pub fn end_control_block(
+        &mut self,
+        block_hash: Word,
+    ) -> Result<Option<ExecutionContextInfo>, DecoderError> {
         // remove the block from the top of the block stack and add an END row to the trace
-        let block_info = self.block_stack.pop();
+        let block_info = self.block_stack.pop()?;
+        debug_assert_ne!(BlockType::Span, block_info.block_type(), "ending a SPAN block");
         self.trace.append_block_end(
             block_info.addr,
             block_hash,
@@ -727,17 +967,24 @@ impl Decoder {
         );
 
         self.debug_info.append_operation(Operation::End);
+        self.record_call(|| DecoderCall::EndControlBlock { block_hash });
 
-        block_info.ctx_info
+        Ok(block_info.ctx_info)
     }
 
     // SPAN BLOCK
     // --------------------------------------------------------------------------------------------
 
     /// Starts decoding of a SPAN block defined by the specified operation batches.
-    pub fn start_basic_block(&mut self, first_op_batch: &OpBatch, num_op_groups: Felt, addr: Felt) {
+    pub fn start_basic_block(
+        &mut self,
+        first_op_batch: &OpBatch,
+        num_op_groups: Felt,
+        addr: Felt,
+    ) -> Result<(), DecoderError> {
         debug_assert!(self.span_context.is_none(), "already in span");
-        let parent_addr = self.block_stack.push(addr, BlockType::Span, None);
+        let first_group = OpGroup::new(first_op_batch.groups()[0])?;
+        let parent_addr = self.block_stack.push(addr, BlockType::Span, None)?;
 
         // add a SPAN row to the trace
         self.trace
@@ -747,50 +994,81 @@ impl Decoder {
         // because executing SPAN consumes the first group of the batch.
         self.span_context = Some(SpanContext {
             num_groups_left: num_op_groups - ONE,
-            group_ops_left: first_op_batch.groups()[0],
+            group_ops_left: first_group,
+            start_addr: addr,
         });
 
         self.debug_info.append_operation(Operation::Span);
+        self.record_op_group(first_op_batch.groups()[0]);
+        self.record_call(|| DecoderCall::StartBasicBlock {
+            first_op_batch: first_op_batch.clone(),
+            num_op_groups,
+            addr,
+        });
+
+        Ok(())
     }
 
     /// Starts decoding of the next operation batch in the current SPAN.
-    pub fn respan(&mut self, op_batch: &OpBatch) {
+    ///
+    /// # Errors
+    /// Returns an error if the decoder is not currently decoding a SPAN block, or if the first
+    /// operation group of the batch is not a valid op group.
+    pub fn respan(&mut self, op_batch: &OpBatch) -> Result<(), DecoderError> {
+        let ctx = self.span_context.as_mut().ok_or(DecoderError::NotInSpan)?;
+        let first_group = OpGroup::new(op_batch.groups()[0])?;
+
         // get the current clock cycle here (before the trace table is updated)
         // add RESPAN row to the trace
         self.trace.append_respan(op_batch.groups());
 
         // we also need to increment block address by 8 because hashing every additional operation
         // batch requires 8 rows of the hasher trace.
-        let block_info = self.block_stack.peek_mut();
+        let block_info = self.block_stack.peek_mut()?;
         block_info.addr += HASH_CYCLE_LEN;
 
-        let ctx = self.span_context.as_mut().expect("not in span");
-
         // after RESPAN operation is executed, we decrement the number of remaining groups by ONE
         // because executing RESPAN consumes the first group of the batch
         ctx.num_groups_left -= ONE;
-        ctx.group_ops_left = op_batch.groups()[0];
+        ctx.group_ops_left = first_group;
 
         self.debug_info.append_operation(Operation::Respan);
+        self.record_op_group(op_batch.groups()[0]);
+        self.record_call(|| DecoderCall::Respan { op_batch: op_batch.clone() });
+
+        Ok(())
     }
 
     /// Starts decoding a new operation group.
-    pub fn start_op_group(&mut self, op_group: Felt) {
-        let ctx = self.span_context.as_mut().expect("not in span");
+    ///
+    /// # Errors
+    /// Returns an error if the provided value cannot be a valid op group (i.e., if it would
+    /// encode more than [OP_GROUP_SIZE] opcodes).
+    pub fn start_op_group(&mut self, op_group: Felt) -> Result<(), DecoderError> {
+        let group = OpGroup::new(op_group)?;
+        let ctx = self.span_context.as_mut().ok_or(DecoderError::NotInSpan)?;
 
         // reset the current group value and decrement the number of left groups by ONE
-        debug_assert_eq!(ZERO, ctx.group_ops_left, "not all ops executed in current group");
-        ctx.group_ops_left = op_group;
+        debug_assert!(ctx.group_ops_left.is_empty(), "not all ops executed in current group");
+        ctx.group_ops_left = group;
         ctx.num_groups_left -= ONE;
+
+        self.record_op_group(op_group);
+        self.record_call(|| DecoderCall::StartOpGroup { op_group });
+
+        Ok(())
     }
 
     /// Decodes a user operation (i.e., not a control flow operation).
-    pub fn execute_user_op(&mut self, op: Operation, op_idx: usize) {
-        let block = self.block_stack.peek();
-        let ctx = self.span_context.as_mut().expect("not in span");
+    ///
+    /// # Errors
+    /// Returns an error if the decoder is not currently decoding a SPAN block.
+    pub fn execute_user_op(&mut self, op: Operation, op_idx: usize) -> Result<(), DecoderError> {
+        let block = self.block_stack.peek()?;
+        let ctx = self.span_context.as_mut().ok_or(DecoderError::NotInSpan)?;
 
         // update operations left to be executed in the group
-        ctx.group_ops_left = remove_opcode_from_group(ctx.group_ops_left, op);
+        ctx.group_ops_left = ctx.group_ops_left.remove_op(op);
 
         // append the row for the operation to the trace
         self.trace.append_user_op(
@@ -798,7 +1076,7 @@ impl Decoder {
             block.addr,
             block.parent_addr,
             ctx.num_groups_left,
-            ctx.group_ops_left,
+            ctx.group_ops_left.into(),
             Felt::from(op_idx as u32),
         );
 
@@ -811,6 +1089,10 @@ impl Decoder {
         }
 
         self.debug_info.append_operation(op);
+        self.debug_info.append_block_op(ctx.start_addr);
+        self.record_call(|| DecoderCall::ExecuteUserOp { op, op_idx });
+
+        Ok(())
     }
 
     /// Sets the helper registers in the trace to the user-provided helper values. This is expected
@@ -824,17 +1106,36 @@ impl Decoder {
             "user op helper registers not available for op"
         );
         self.trace.set_user_op_helpers(values);
+
+        self.record_call(|| DecoderCall::SetUserOpHelpers { op, values: values.to_vec() });
     }
 
     /// Ends decoding of a SPAN block.
-    pub fn end_basic_block(&mut self, block_hash: Word) {
+    ///
+    /// # Errors
+    /// Returns an error if not all operations of the SPAN block have been executed, i.e., if there
+    /// are operations other than NOOPs left in the current op group, or if there are op groups
+    /// left to be executed.
+    pub fn end_basic_block(&mut self, block_hash: Word) -> Result<(), DecoderError> {
+        let ctx = self.span_context.as_ref().ok_or(DecoderError::NotInSpan)?;
+        if !ctx.group_ops_left.is_empty() || ctx.num_groups_left != ZERO {
+            return Err(DecoderError::IncompleteSpanBlock {
+                group_ops_left: ctx.group_ops_left.into(),
+                num_groups_left: ctx.num_groups_left,
+            });
+        }
+
         // remove the block from the stack of executing blocks and add an END row to the
         // execution trace
-        let block_info = self.block_stack.pop();
+        let block_info = self.block_stack.pop()?;
+        debug_assert_eq!(BlockType::Span, block_info.block_type(), "not a SPAN block");
         self.trace.append_span_end(block_hash, block_info.is_loop_body());
         self.span_context = None;
 
         self.debug_info.append_operation(Operation::End);
+        self.record_call(|| DecoderCall::EndBasicBlock { block_hash });
+
+        Ok(())
     }
 
     // TRACE GENERATIONS
@@ -844,9 +1145,66 @@ impl Decoder {
     ///
     /// Trace columns are extended to match the specified trace length.
     pub fn into_trace(self, trace_len: usize, num_rand_rows: usize) -> super::DecoderTrace {
+        self.build_trace(trace_len, num_rand_rows, None)
+    }
+
+    /// Returns a column-major matrix containing an execution trace of this decoder.
+    ///
+    /// Trace columns are extended to match the specified trace length in the same way as in
+    /// [Decoder::into_trace()]; the last `num_rand_rows` rows of each column are left to be
+    /// overwritten with random values.
+    pub fn into_matrix(self, trace_len: usize, num_rand_rows: usize) -> ColMatrix<Felt> {
+        ColMatrix::new(self.pad_trace(trace_len, num_rand_rows, None))
+    }
+
+    /// Returns a vector of rows containing an execution trace of this decoder.
+    ///
+    /// The rows are the same as the rows of the trace returned by [Decoder::into_trace()].
+    pub fn into_row_major(self, trace_len: usize, num_rand_rows: usize) -> Vec<Vec<Felt>> {
+        self.build_row_major(trace_len, num_rand_rows, None)
+    }
+
+    /// Returns an array of columns containing an execution trace of this decoder together with
+    /// the time spent in each phase of the trace finalization.
+    ///
+    /// The returned trace is identical to the one returned by [Decoder::into_trace()]. Since the
+    /// trace is returned in column-major form, no time is spent transposing it.
+    #[cfg(feature = "std")]
+    pub fn into_trace_profiled(
+        self,
+        trace_len: usize,
+        num_rand_rows: usize,
+    ) -> (super::DecoderTrace, TraceProfile) {
+        let mut profile = TraceProfile::default();
+        let trace = self.build_trace(trace_len, num_rand_rows, Some(&mut profile));
+        (trace, profile)
+    }
+
+    /// Returns a vector of rows containing an execution trace of this decoder together with the
+    /// time spent in each phase of the trace finalization.
+    ///
+    /// The returned rows are identical to the ones returned by [Decoder::into_row_major()].
+    #[cfg(feature = "std")]
+    pub fn into_row_major_profiled(
+        self,
+        trace_len: usize,
+        num_rand_rows: usize,
+    ) -> (Vec<Vec<Felt>>, TraceProfile) {
+        let mut profile = TraceProfile::default();
+        let rows = self.build_row_major(trace_len, num_rand_rows, Some(&mut profile));
+        (rows, profile)
+    }
+
+    /// Builds an array of columns containing an execution trace of this decoder, recording the
+    /// time spent in each phase into the provided profile (if any).
+    fn build_trace(
+        self,
+        trace_len: usize,
+        num_rand_rows: usize,
+        profile: Option<&mut TraceProfile>,
+    ) -> super::DecoderTrace {
         let trace = self
-            .trace
-            .into_vec(trace_len, num_rand_rows)
+            .pad_trace(trace_len, num_rand_rows, profile)
             .try_into()
             .expect("failed to convert vector to array");
         let aux_builder = AuxTraceBuilder::default();
@@ -854,6 +1212,34 @@ impl Decoder {
         super::DecoderTrace { trace, aux_builder }
     }
 
+    /// Builds a vector of rows containing an execution trace of this decoder, recording the time
+    /// spent in each phase into the provided profile (if any).
+    fn build_row_major(
+        self,
+        trace_len: usize,
+        num_rand_rows: usize,
+        mut profile: Option<&mut TraceProfile>,
+    ) -> Vec<Vec<Felt>> {
+        let columns = self.pad_trace(trace_len, num_rand_rows, profile.as_deref_mut());
+        timed(profile.map(|profile| &mut profile.transposing), || transpose(&columns))
+    }
+
+    /// Extends the columns of this decoder's trace to the specified trace length, recording the
+    /// time spent doing so into the provided profile (if any).
+    fn pad_trace(
+        self,
+        trace_len: usize,
+        num_rand_rows: usize,
+        profile: Option<&mut TraceProfile>,
+    ) -> Vec<Vec<Felt>> {
+        debug_assert_eq!(0, self.block_stack.depth(), "unbalanced block stack");
+
+        let trace = self.trace;
+        timed(profile.map(|profile| &mut profile.padding), || {
+            trace.into_vec(trace_len, num_rand_rows)
+        })
+    }
+
     // HELPERS
     // --------------------------------------------------------------------------------------------
 
@@ -862,6 +1248,22 @@ impl Decoder {
         self.debug_info.append_asmop(clk, asmop);
     }
 
+    /// Updates the largest operation group value with the specified operation group.
+    #[inline(always)]
+    fn record_op_group(&mut self, op_group: Felt) {
+        if op_group.as_int() > self.max_op_group_value.as_int() {
+            self.max_op_group_value = op_group;
+        }
+    }
+
+    /// Appends a call built by the provided closure to the op log if recording of calls is enabled.
+    #[inline(always)]
+    fn record_call(&mut self, call: impl FnOnce() -> DecoderCall) {
+        if let Some(op_log) = self.op_log.as_mut() {
+            op_log.push(call());
+        }
+    }
+
     // TEST METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -884,6 +1286,22 @@ impl Default for Decoder {
     }
 }
 
+// TRACE PROFILE
+// ================================================================================================
+
+/// Time spent in each phase of converting the decoder's execution trace into its final form.
+///
+/// Rows are appended to the trace as the program is being executed, and thus, the time spent
+/// appending them is a part of the execution time and is not included in this profile.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TraceProfile {
+    /// Time spent extending the trace columns to the target trace length.
+    pub padding: Duration,
+    /// Time spent transposing the padded columns into rows. This is zero when the trace is
+    /// returned in column-major form.
+    pub transposing: Duration,
+}
+
 // SPAN CONTEXT
 // ================================================================================================
 
@@ -892,23 +1310,92 @@ impl Default for Decoder {
 ///   opcodes (7 bits) appended one after another into a single field element, with the next
 ///   operation to be executed located at the least significant position.
 /// - Number of operation groups left to be executed in the entire SPAN block.
+/// - Address of the SPAN block at the time it was started. Unlike the address of the block on the
+///   block stack, this address is not updated when a new operation batch is started.
 #[derive(Default)]
 struct SpanContext {
-    group_ops_left: Felt,
+    group_ops_left: OpGroup,
     num_groups_left: Felt,
+    start_addr: Felt,
 }
 
-// HELPER FUNCTIONS
+// OP GROUP
 // ================================================================================================
 
-/// Removes the specified operation from the op group and returns the resulting op group.
-fn remove_opcode_from_group(op_group: Felt, op: Operation) -> Felt {
-    let opcode = op.op_code() as u64;
-    let result = Felt::new((op_group.as_int() - opcode) >> NUM_OP_BITS);
-    debug_assert!(op_group.as_int() >= result.as_int(), "op group underflow");
-    result
+/// Opcodes of the operations which still need to be executed in an operation group.
+///
+/// The opcodes are packed into a single field element, with the next operation to be executed
+/// located at the least significant position.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct OpGroup(Felt);
+
+impl OpGroup {
+    /// Returns a new [OpGroup] instantiated from the specified value.
+    ///
+    /// # Errors
+    /// Returns an error if the value cannot be a valid op group. An op group contains at most
+    /// [OP_GROUP_SIZE] opcodes of [NUM_OP_BITS] bits each, and thus, the value of a valid op group
+    /// must be smaller than 2^(NUM_OP_BITS * OP_GROUP_SIZE). Larger values would not be reduced to
+    /// ZERO by removing [OP_GROUP_SIZE] opcodes from the group.
+    pub fn new(value: Felt) -> Result<Self, DecoderError> {
+        if value.as_int() >> (NUM_OP_BITS * OP_GROUP_SIZE) != 0 {
+            return Err(DecoderError::OpGroupOverflow(value));
+        }
+
+        Ok(Self(value))
+    }
+
+    /// Returns true if there are no operations left in this group, other than (possibly) NOOPs.
+    pub fn is_empty(&self) -> bool {
+        self.0 == ZERO
+    }
+
+    /// Returns the opcode of the next operation to be executed in this group.
+    ///
+    /// The opcode of NOOP is ZERO, and thus, this returns ZERO for an empty group.
+    fn peek_opcode(&self) -> u8 {
+        (self.0.as_int() & ((1 << NUM_OP_BITS) - 1)) as u8
+    }
+
+    /// Returns the next operation to be executed in this group.
+    ///
+    /// None is returned if the operation carries associated data which cannot be recovered from
+    /// its opcode alone (i.e., PUSH, EMIT, ASSERT, U32ASSERT2, and MPVERIFY). The opcode of NOOP is
+    /// ZERO, and thus, NOOP is returned for an empty group.
+    pub fn peek_op(&self) -> Option<Operation> {
+        Operation::from_op_code(self.peek_opcode())
+    }
+
+    /// Removes the next operation from this group, and returns the operation together with the
+    /// resulting group.
+    ///
+    /// None is returned, and the group is left unchanged, in the same cases as for
+    /// [OpGroup::peek_op()].
+    pub fn pop_op(self) -> Option<(Operation, Self)> {
+        self.peek_op().map(|op| (op, self.remove_op(op)))
+    }
+
+    /// Removes the specified operation from this group and returns the resulting group.
+    ///
+    /// The opcode of NOOP is ZERO, and thus removing a NOOP from a group simply shifts the
+    /// remaining operations. This means that groups padded with NOOPs (including groups consisting
+    /// entirely of NOOPs) do not require any special handling.
+    fn remove_op(self, op: Operation) -> Self {
+        debug_assert_eq!(op.op_code(), self.peek_opcode(), "unexpected operation in op group");
+        let opcode = op.op_code() as u64;
+        Self(Felt::new((self.0.as_int() - opcode) >> NUM_OP_BITS))
+    }
+}
+
+impl From<OpGroup> for Felt {
+    fn from(group: OpGroup) -> Self {
+        group.0
+    }
 }
 
+// HELPER FUNCTIONS
+// ================================================================================================
+
 /// Returns the number of op groups in the next batch based on how many total groups are left to
 /// process in a span.
 ///
@@ -919,6 +1406,31 @@ fn get_num_groups_in_next_batch(num_groups_left: Felt) -> usize {
     core::cmp::min(num_groups_left.as_int() as usize, OP_BATCH_SIZE)
 }
 
+/// Returns the rows of the trace formed by the specified columns.
+fn transpose(columns: &[Vec<Felt>]) -> Vec<Vec<Felt>> {
+    let num_rows = columns.first().map_or(0, |column| column.len());
+    (0..num_rows)
+        .map(|i| columns.iter().map(|column| column[i]).collect())
+        .collect()
+}
+
+/// Executes the specified closure and adds the time spent executing it to the provided timer (if
+/// any). Time can be measured only when the `std` feature is enabled.
+fn timed<T>(timer: Option<&mut Duration>, f: impl FnOnce() -> T) -> T {
+    #[cfg(feature = "std")]
+    if let Some(timer) = timer {
+        let now = Instant::now();
+        let result = f();
+        *timer += now.elapsed();
+        return result;
+    }
+
+    #[cfg(not(feature = "std"))]
+    let _ = timer;
+
+    f()
+}
+
 // TEST HELPERS
 // ================================================================================================
 
@@ -942,6 +1454,8 @@ pub struct DebugInfo {
     in_debug_mode: bool,
     operations: Vec<Operation>,
     assembly_ops: Vec<(usize, AssemblyOp)>,
+    block_op_counts: BTreeMap<u64, usize>,
+    split_branches: Vec<(Felt, bool)>,
 }
 
 impl DebugInfo {
@@ -950,6 +1464,8 @@ impl DebugInfo {
             in_debug_mode,
             operations: Vec::<Operation>::new(),
             assembly_ops: Vec::<(usize, AssemblyOp)>::new(),
+            block_op_counts: BTreeMap::new(),
+            split_branches: Vec::new(),
         }
     }
 
@@ -970,6 +1486,42 @@ impl DebugInfo {
         &self.assembly_ops
     }
 
+    /// Returns the number of user operations executed in each SPAN block, keyed by the address of
+    /// the SPAN row of the block.
+    ///
+    /// The counts are collected only in debug mode; otherwise, the returned map is always empty.
+    /// Operations from all batches of a SPAN block are attributed to the same address. If a SPAN
+    /// block is executed more than once (e.g., as a body of a loop), each execution gets a distinct
+    /// address, and thus, is counted separately.
+    pub fn block_op_counts(&self) -> &BTreeMap<u64, usize> {
+        &self.block_op_counts
+    }
+
+    /// Returns the address of each executed SPLIT block together with a flag indicating whether the
+    /// true branch of the block was taken.
+    ///
+    /// This list is populated only in debug mode; otherwise, it is always empty. The blocks are
+    /// listed in the order in which they were executed. SPLIT blocks with a non-binary condition
+    /// cause execution to fail before either branch is taken, and thus, are not listed.
+    pub fn split_branches(&self) -> &[(Felt, bool)] {
+        &self.split_branches
+    }
+
+    /// Returns the total cost of all operations executed by the decoder, including control flow
+    /// operations such as SPAN, RESPAN, and END.
+    ///
+    /// The cost is computed from the operations recorded by the decoder, which are recorded only in
+    /// debug mode; otherwise, the total cost is always 0.
+    ///
+    /// The cost of each operation is looked up by its opcode in `op_costs`; operations missing from
+    /// the table cost 1. HALT operations padding the trace are not included.
+    pub fn total_cost(&self, op_costs: &BTreeMap<u8, u64>) -> u64 {
+        self.operations
+            .iter()
+            .map(|op| op_costs.get(&op.op_code()).copied().unwrap_or(1))
+            .sum()
+    }
+
     /// Adds an operation to the operations vector in debug mode.
     #[inline(always)]
     pub fn append_operation(&mut self, op: Operation) {
@@ -978,6 +1530,22 @@ impl DebugInfo {
         }
     }
 
+    /// Attributes a user operation to the SPAN block with the specified address in debug mode.
+    #[inline(always)]
+    pub fn append_block_op(&mut self, addr: Felt) {
+        if self.in_debug_mode {
+            *self.block_op_counts.entry(addr.as_int()).or_default() += 1;
+        }
+    }
+
+    /// Records the branch taken by the SPLIT block with the specified address in debug mode.
+    #[inline(always)]
+    pub fn append_split_branch(&mut self, addr: Felt, took_true: bool) {
+        if self.in_debug_mode {
+            self.split_branches.push((addr, took_true));
+        }
+    }
+
     /// Appends an asmop decorator at the specified clock cycle to the asmop list in debug mode.
     pub fn append_asmop(&mut self, clk: RowIndex, asmop: AssemblyOp) {
         self.assembly_ops.push((clk.into(), asmop));
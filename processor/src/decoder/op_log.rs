@@ -0,0 +1,160 @@
+use alloc::vec::Vec;
+
+use super::{Decoder, DecoderError, ExecutionContextInfo, Felt, OpBatch, Operation, Word};
+
+// DECODER OP LOG
+// ================================================================================================
+
+/// A log of calls made to a [Decoder] while executing a program.
+///
+/// Replaying the log via [Decoder::replay()] reproduces the state of the decoder (including its
+/// execution trace) without executing the program again. Calls are recorded only if recording is
+/// enabled via [Decoder::with_op_log()] (e.g., by executing a program with
+/// `ExecutionOptions::with_decoder_op_log()`).
+#[derive(Debug, Clone, Default)]
+pub struct DecoderOpLog {
+    calls: Vec<DecoderCall>,
+}
+
+impl DecoderOpLog {
+    /// Returns the calls recorded in this log in the order in which they were made.
+    pub fn calls(&self) -> &[DecoderCall] {
+        &self.calls
+    }
+
+    /// Appends the specified call to this log.
+    pub fn push(&mut self, call: DecoderCall) {
+        self.calls.push(call);
+    }
+}
+
+// DECODER CALL
+// ================================================================================================
+
+/// A call to one of the [Decoder] methods which update the execution trace, together with the
+/// arguments of the call.
+#[derive(Debug, Clone)]
+pub enum DecoderCall {
+    StartJoin {
+        child1_hash: Word,
+        child2_hash: Word,
+        addr: Felt,
+    },
+    StartSplit {
+        child1_hash: Word,
+        child2_hash: Word,
+        addr: Felt,
+        stack_top: Felt,
+    },
+    StartLoop {
+        loop_body_hash: Word,
+        addr: Felt,
+        stack_top: Felt,
+    },
+    Repeat,
+    StartCall {
+        fn_hash: Word,
+        addr: Felt,
+        ctx_info: ExecutionContextInfo,
+    },
+    StartSyscall {
+        fn_hash: Word,
+        addr: Felt,
+        ctx_info: ExecutionContextInfo,
+    },
+    StartDyn {
+        addr: Felt,
+        callee_hash: Word,
+    },
+    StartDyncall {
+        addr: Felt,
+        callee_hash: Word,
+        ctx_info: ExecutionContextInfo,
+    },
+    EndControlBlock {
+        block_hash: Word,
+    },
+    StartBasicBlock {
+        first_op_batch: OpBatch,
+        num_op_groups: Felt,
+        addr: Felt,
+    },
+    Respan {
+        op_batch: OpBatch,
+    },
+    StartOpGroup {
+        op_group: Felt,
+    },
+    ExecuteUserOp {
+        op: Operation,
+        op_idx: usize,
+    },
+    SetUserOpHelpers {
+        op: Operation,
+        values: Vec<Felt>,
+    },
+    EndBasicBlock {
+        block_hash: Word,
+    },
+}
+
+// REPLAY
+// ================================================================================================
+
+impl Decoder {
+    /// Returns a new [Decoder] to which all calls recorded in the provided log have been applied.
+    ///
+    /// # Errors
+    /// Returns an error if any of the replayed calls fails.
+    pub fn replay(log: &DecoderOpLog) -> Result<Self, DecoderError> {
+        let mut decoder = Self::default();
+        for call in log.calls() {
+            match call {
+                DecoderCall::StartJoin { child1_hash, child2_hash, addr } => {
+                    decoder.start_join(*child1_hash, *child2_hash, *addr)?
+                },
+                DecoderCall::StartSplit {
+                    child1_hash,
+                    child2_hash,
+                    addr,
+                    stack_top,
+                } => decoder.start_split(*child1_hash, *child2_hash, *addr, *stack_top)?,
+                DecoderCall::StartLoop { loop_body_hash, addr, stack_top } => {
+                    decoder.start_loop(*loop_body_hash, *addr, *stack_top)?
+                },
+                DecoderCall::Repeat => decoder.repeat()?,
+                DecoderCall::StartCall { fn_hash, addr, ctx_info } => {
+                    decoder.start_call(*fn_hash, *addr, *ctx_info)?
+                },
+                DecoderCall::StartSyscall { fn_hash, addr, ctx_info } => {
+                    decoder.start_syscall(*fn_hash, *addr, *ctx_info)?
+                },
+                DecoderCall::StartDyn { addr, callee_hash } => {
+                    decoder.start_dyn(*addr, *callee_hash)?
+                },
+                DecoderCall::StartDyncall { addr, callee_hash, ctx_info } => {
+                    decoder.start_dyncall(*addr, *callee_hash, *ctx_info)?
+                },
+                DecoderCall::EndControlBlock { block_hash } => {
+                    decoder.end_control_block(*block_hash)?;
+                },
+                DecoderCall::StartBasicBlock { first_op_batch, num_op_groups, addr } => {
+                    decoder.start_basic_block(first_op_batch, *num_op_groups, *addr)?
+                },
+                DecoderCall::Respan { op_batch } => decoder.respan(op_batch)?,
+                DecoderCall::StartOpGroup { op_group } => decoder.start_op_group(*op_group)?,
+                DecoderCall::ExecuteUserOp { op, op_idx } => {
+                    decoder.execute_user_op(*op, *op_idx)?
+                },
+                DecoderCall::SetUserOpHelpers { op, values } => {
+                    decoder.set_user_op_helpers(*op, values)
+                },
+                DecoderCall::EndBasicBlock { block_hash } => {
+                    decoder.end_basic_block(*block_hash)?
+                },
+            }
+        }
+
+        Ok(decoder)
+    }
+}
@@ -1,6 +1,12 @@
-use alloc::vec::Vec;
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::time::Duration;
 
 use miden_air::trace::{
+    chiplets::hasher,
     decoder::{
         ADDR_COL_IDX, GROUP_COUNT_COL_IDX, HASHER_STATE_RANGE, IN_SPAN_COL_IDX, NUM_HASHER_COLUMNS,
         NUM_OP_BATCH_FLAGS, NUM_OP_BITS, OP_BATCH_1_GROUPS, OP_BATCH_2_GROUPS, OP_BATCH_4_GROUPS,
@@ -8,21 +14,27 @@ use miden_air::trace::{
         OP_INDEX_COL_IDX,
     },
     CTX_COL_IDX, DECODER_TRACE_RANGE, DECODER_TRACE_WIDTH, FMP_COL_IDX, FN_HASH_RANGE,
-    IN_SYSCALL_COL_IDX, SYS_TRACE_RANGE, SYS_TRACE_WIDTH,
+    IN_SYSCALL_COL_IDX, MIN_TRACE_LEN, SYS_TRACE_RANGE, SYS_TRACE_WIDTH,
 };
 use test_utils::rand::rand_value;
 use vm_core::{
-    mast::{BasicBlockNode, MastForest, MastNode, OP_BATCH_SIZE},
+    assert_matches,
+    mast::{BasicBlockNode, MastForest, MastNode, OP_BATCH_SIZE, OP_GROUP_SIZE},
     Program, EMPTY_WORD, ONE, ZERO,
 };
 
 use super::{
     super::{
-        ExecutionOptions, ExecutionTrace, Felt, Kernel, Operation, Process, StackInputs, Word,
+        Digest, ExecutionOptions, ExecutionTrace, Felt, Kernel, Operation, Process, StackInputs,
+        Word,
     },
-    build_op_group,
+    build_op_group, operation_from_op_code, BlockStack, BlockType, Decoder, ExecutionContextInfo,
+    OpGroup, HASH_CYCLE_LEN,
+};
+use crate::{
+    crypto::{ElementHasher, Rpo256},
+    DecoderError, DefaultHost, ExecutionError, RedundancyKind, NUM_RAND_ROWS,
 };
-use crate::DefaultHost;
 
 // CONSTANTS
 // ================================================================================================
@@ -414,6 +426,184 @@ fn span_block_with_respan() {
     }
 }
 
+#[test]
+fn basic_block_with_noop_padding() {
+    // --- group with NOOPs in its second half ----------------------------------------------------
+    let ops = vec![
+        Operation::Pad,
+        Operation::Add,
+        Operation::Pad,
+        Operation::Mul,
+        Operation::Noop,
+        Operation::Noop,
+        Operation::Noop,
+        Operation::Noop,
+        Operation::Noop,
+    ];
+    let program = {
+        let mut mast_forest = MastForest::new();
+
+        let basic_block_id = mast_forest.add_block(ops.clone(), None).unwrap();
+        mast_forest.make_root(basic_block_id);
+
+        Program::new(mast_forest.into(), basic_block_id)
+    };
+    let (trace, _) = build_trace(&[], &program);
+
+    check_op_decoding(&trace, 0, ZERO, Operation::Span, 1, 0, 0);
+    for (i, &op) in ops.iter().enumerate() {
+        check_op_decoding(&trace, i + 1, INIT_ADDR, op, 0, i as u64, 1);
+        assert_eq!(build_op_group(&ops[i + 1..]), get_hasher_state(&trace, i + 1)[0]);
+    }
+    check_op_decoding(&trace, 10, INIT_ADDR, Operation::End, 0, 0, 0);
+
+    // once only NOOPs are left in the group, the group value is ZERO
+    assert_eq!(ZERO, get_hasher_state(&trace, 4)[0]);
+
+    // --- group consisting entirely of NOOPs -----------------------------------------------------
+    let mut ops = vec![Operation::Noop; 9];
+    ops.push(Operation::Add);
+    let program = {
+        let mut mast_forest = MastForest::new();
+
+        let basic_block_id = mast_forest.add_block(ops, None).unwrap();
+        mast_forest.make_root(basic_block_id);
+
+        Program::new(mast_forest.into(), basic_block_id)
+    };
+    let (trace, _) = build_trace(&[], &program);
+
+    check_op_decoding(&trace, 0, ZERO, Operation::Span, 2, 0, 0);
+    for i in 0..9 {
+        check_op_decoding(&trace, i + 1, INIT_ADDR, Operation::Noop, 1, i as u64, 1);
+        assert_eq!(ZERO, get_hasher_state(&trace, i + 1)[0]);
+    }
+    check_op_decoding(&trace, 10, INIT_ADDR, Operation::Add, 0, 0, 1);
+    check_op_decoding(&trace, 11, INIT_ADDR, Operation::End, 0, 0, 0);
+}
+
+#[test]
+fn op_group_overflow() {
+    let basic_block =
+        BasicBlockNode::new(vec![Operation::Pad, Operation::Push(ONE), Operation::Add], None)
+            .unwrap();
+    let mut decoder = Decoder::default();
+    decoder
+        .start_basic_block(&basic_block.op_batches()[0], Felt::new(3), INIT_ADDR)
+        .unwrap();
+    decoder.execute_user_op(Operation::Pad, 0).unwrap();
+    decoder.execute_user_op(Operation::Push(ONE), 1).unwrap();
+    decoder.execute_user_op(Operation::Add, 2).unwrap();
+
+    // a group can hold at most 9 opcodes of 7 bits each
+    let over_packed = Felt::new(1 << (NUM_OP_BITS * OP_GROUP_SIZE));
+    assert_matches!(
+        decoder.start_op_group(over_packed),
+        Err(DecoderError::OpGroupOverflow(group)) if group == over_packed
+    );
+
+    let max_group = Felt::new((1 << (NUM_OP_BITS * OP_GROUP_SIZE)) - 1);
+    decoder.start_op_group(max_group).unwrap();
+}
+
+#[test]
+fn peek_group_ops_left() {
+    let basic_block =
+        BasicBlockNode::new(vec![Operation::Pad, Operation::Push(ONE), Operation::Add], None)
+            .unwrap();
+    let mut decoder = Decoder::default();
+    assert_eq!(None, decoder.peek_group_ops_left());
+
+    decoder
+        .start_basic_block(&basic_block.op_batches()[0], Felt::new(3), INIT_ADDR)
+        .unwrap();
+    decoder.execute_user_op(Operation::Pad, 0).unwrap();
+    decoder.execute_user_op(Operation::Push(ONE), 1).unwrap();
+    decoder.execute_user_op(Operation::Add, 2).unwrap();
+    assert_eq!(Some(ZERO), decoder.peek_group_ops_left());
+
+    // peeking does not remove operations from the group
+    let op_group = build_op_group(&[Operation::Pad, Operation::Drop]);
+    decoder.start_op_group(op_group).unwrap();
+    assert_eq!(Some(op_group), decoder.peek_group_ops_left());
+    assert_eq!(Some(op_group), decoder.peek_group_ops_left());
+
+    decoder.execute_user_op(Operation::Pad, 3).unwrap();
+    let expected = build_op_group(&[Operation::Drop]);
+    assert_eq!(Some(expected), decoder.peek_group_ops_left());
+}
+
+#[test]
+fn op_group_ops() {
+    let ops = [Operation::Pad, Operation::Add, Operation::Drop];
+    let mut group = OpGroup::new(build_op_group(&ops)).unwrap();
+
+    for op in ops {
+        assert!(!group.is_empty());
+        assert_eq!(op.op_code(), group.peek_opcode());
+        group = group.remove_op(op);
+    }
+
+    // only NOOPs are left in the group once all operations have been removed
+    assert!(group.is_empty());
+    assert_eq!(Operation::Noop.op_code(), group.peek_opcode());
+    assert_eq!(group, group.remove_op(Operation::Noop));
+    assert_eq!(ZERO, Felt::from(group));
+}
+
+#[test]
+fn op_group_peek_and_pop() {
+    let ops = [Operation::Pad, Operation::Add, Operation::Push(ONE), Operation::Drop];
+    let group = OpGroup::new(build_op_group(&ops)).unwrap();
+
+    // peeking does not change the group
+    assert_eq!(Some(Operation::Pad), group.peek_op());
+    assert_eq!(Some(Operation::Pad), group.peek_op());
+
+    let (op, group) = group.pop_op().unwrap();
+    assert_eq!(Operation::Pad, op);
+    let (op, group) = group.pop_op().unwrap();
+    assert_eq!(Operation::Add, op);
+
+    // the immediate value of PUSH cannot be recovered from the group
+    assert_eq!(None, group.peek_op());
+    assert_eq!(None, group.pop_op());
+    let group = group.remove_op(Operation::Push(ONE));
+
+    let (op, group) = group.pop_op().unwrap();
+    assert_eq!(Operation::Drop, op);
+    assert!(group.is_empty());
+
+    // an empty group yields NOOPs
+    assert_eq!(Some((Operation::Noop, group)), group.pop_op());
+}
+
+#[test]
+fn incomplete_span_block() {
+    let basic_block = BasicBlockNode::new(vec![Operation::Pad, Operation::Add], None).unwrap();
+    let mut decoder = Decoder::default();
+    decoder.start_basic_block(&basic_block.op_batches()[0], ONE, INIT_ADDR).unwrap();
+    decoder.execute_user_op(Operation::Pad, 0).unwrap();
+
+    // ADD is still left in the op group
+    let add_group = build_op_group(&[Operation::Add]);
+    assert_matches!(
+        decoder.end_basic_block(basic_block.digest().into()),
+        Err(DecoderError::IncompleteSpanBlock { group_ops_left, num_groups_left })
+            if group_ops_left == add_group && num_groups_left == ZERO
+    );
+
+    decoder.execute_user_op(Operation::Add, 1).unwrap();
+    decoder.end_basic_block(basic_block.digest().into()).unwrap();
+
+    // trailing NOOPs in the last op group do not need to be executed
+    let basic_block = BasicBlockNode::new(vec![Operation::Pad, Operation::Noop], None).unwrap();
+    let mut decoder = Decoder::default();
+    decoder.start_basic_block(&basic_block.op_batches()[0], ONE, INIT_ADDR).unwrap();
+    decoder.execute_user_op(Operation::Pad, 0).unwrap();
+    decoder.end_basic_block(basic_block.digest().into()).unwrap();
+}
+
 // JOIN BLOCK TESTS
 // ================================================================================================
 
@@ -755,6 +945,68 @@ fn loop_node_repeat() {
     }
 }
 
+#[test]
+fn loop_body_hash() {
+    let loop_body = MastNode::new_basic_block(vec![Operation::Pad, Operation::Drop], None).unwrap();
+    let program = {
+        let mut mast_forest = MastForest::new();
+
+        let loop_body_id = mast_forest.add_node(loop_body.clone()).unwrap();
+        let loop_node_id = mast_forest.add_loop(loop_body_id).unwrap();
+        mast_forest.make_root(loop_node_id);
+
+        Program::new(mast_forest.into(), loop_node_id)
+    };
+
+    let decoder = build_decoder(&program);
+
+    // the body hash is available only in the LOOP row
+    let loop_body_hash: Word = loop_body.digest().into();
+    assert_eq!(Some(loop_body_hash), decoder.loop_body_hash(0));
+    assert_eq!(None, decoder.loop_body_hash(1));
+    assert_eq!(None, decoder.loop_body_hash(decoder.trace_len()));
+}
+
+#[test]
+fn loop_repeat_limit() {
+    let mut decoder = Decoder::default().with_max_loop_repeats(3);
+    decoder.start_loop(EMPTY_WORD, INIT_ADDR, ONE).unwrap();
+
+    // the first execution of the body is not preceded by a REPEAT
+    for _ in 0..3 {
+        decoder.start_join(EMPTY_WORD, EMPTY_WORD, EIGHT + ONE).unwrap();
+        decoder.end_control_block(EMPTY_WORD).unwrap();
+        decoder.repeat().unwrap();
+    }
+
+    decoder.start_join(EMPTY_WORD, EMPTY_WORD, EIGHT + ONE).unwrap();
+    decoder.end_control_block(EMPTY_WORD).unwrap();
+    assert_matches!(decoder.repeat(), Err(DecoderError::LoopIterationLimitExceeded(3)));
+
+    // the limit is set via execution options; the body of the loop below is repeated 3 times
+    let program = {
+        let mut mast_forest = MastForest::new();
+
+        let basic_block_id = mast_forest.add_block(vec![Operation::Noop], None).unwrap();
+        let loop_node_id = mast_forest.add_loop(basic_block_id).unwrap();
+        mast_forest.make_root(loop_node_id);
+
+        Program::new(mast_forest.into(), loop_node_id)
+    };
+    let stack_inputs = StackInputs::try_from_ints([0, 1, 1, 1, 1]).unwrap();
+
+    let options = ExecutionOptions::default().with_max_loop_repeats(2);
+    let mut process = Process::new(Kernel::default(), stack_inputs.clone(), options);
+    assert_matches!(
+        process.execute(&program, &mut DefaultHost::default()),
+        Err(ExecutionError::DecoderError(DecoderError::LoopIterationLimitExceeded(2)))
+    );
+
+    let options = ExecutionOptions::default().with_max_loop_repeats(3);
+    let mut process = Process::new(Kernel::default(), stack_inputs, options);
+    process.execute(&program, &mut DefaultHost::default()).unwrap();
+}
+
 // CALL BLOCK TESTS
 // ================================================================================================
 
@@ -1418,6 +1670,895 @@ fn dyn_block() {
     }
 }
 
+// BLOCK STACK TESTS
+// ================================================================================================
+
+#[test]
+fn block_stack_block_types() {
+    let basic_block = BasicBlockNode::new(vec![Operation::Pad], None).unwrap();
+    let mut decoder = Decoder::default();
+
+    decoder.start_join(EMPTY_WORD, EMPTY_WORD, INIT_ADDR).unwrap();
+    assert_eq!(BlockType::Join(false), decoder.block_stack.peek().unwrap().block_type());
+
+    // first child of the JOIN is a SPAN block
+    decoder.start_basic_block(&basic_block.op_batches()[0], ONE, EIGHT).unwrap();
+    assert_eq!(BlockType::Span, decoder.block_stack.peek().unwrap().block_type());
+    decoder.execute_user_op(Operation::Pad, 0).unwrap();
+    decoder.end_basic_block(basic_block.digest().into()).unwrap();
+
+    // once the first child is done, the JOIN block is marked accordingly
+    assert_eq!(BlockType::Join(true), decoder.block_stack.peek().unwrap().block_type());
+
+    // second child of the JOIN is an entered LOOP block
+    decoder.start_loop(EMPTY_WORD, Felt::new(16), ONE).unwrap();
+    assert_eq!(BlockType::Loop(true), decoder.block_stack.peek().unwrap().block_type());
+    decoder.end_control_block(EMPTY_WORD).unwrap();
+
+    assert_eq!(BlockType::Join(true), decoder.block_stack.peek().unwrap().block_type());
+    decoder.end_control_block(EMPTY_WORD).unwrap();
+}
+
+#[test]
+fn block_stack_call_depth() {
+    let mut decoder = Decoder::default();
+    assert_eq!(0, decoder.call_depth());
+
+    // JOIN and LOOP blocks do not start a new execution context
+    decoder.start_join(EMPTY_WORD, EMPTY_WORD, INIT_ADDR).unwrap();
+    decoder.start_loop(EMPTY_WORD, EIGHT, ONE).unwrap();
+    assert_eq!(0, decoder.call_depth());
+
+    decoder
+        .start_call(EMPTY_WORD, Felt::new(16), ExecutionContextInfo::default())
+        .unwrap();
+    assert_eq!(1, decoder.call_depth());
+
+    decoder
+        .start_syscall(EMPTY_WORD, Felt::new(24), ExecutionContextInfo::default())
+        .unwrap();
+    assert_eq!(2, decoder.call_depth());
+
+    decoder.end_control_block(EMPTY_WORD).unwrap();
+    assert_eq!(1, decoder.call_depth());
+
+    decoder.end_control_block(EMPTY_WORD).unwrap();
+    assert_eq!(0, decoder.call_depth());
+}
+
+#[test]
+fn block_stack_depth_after_decoding() {
+    let basic_block =
+        MastNode::new_basic_block(vec![Operation::Pad, Operation::Drop], None).unwrap();
+    let program = {
+        let mut mast_forest = MastForest::new();
+
+        let basic_block_id = mast_forest.add_node(basic_block).unwrap();
+        let loop_node_id = mast_forest.add_loop(basic_block_id).unwrap();
+        let inner_join_id = mast_forest.add_join(basic_block_id, loop_node_id).unwrap();
+        let outer_join_id = mast_forest.add_join(inner_join_id, basic_block_id).unwrap();
+        mast_forest.make_root(outer_join_id);
+
+        Program::new(mast_forest.into(), outer_join_id)
+    };
+
+    let decoder = build_decoder(&program);
+    assert_eq!(0, decoder.block_stack.depth());
+
+    // the depth is positive while blocks are still being executed
+    let mut decoder = Decoder::default();
+    decoder.start_join(EMPTY_WORD, EMPTY_WORD, INIT_ADDR).unwrap();
+    decoder.start_loop(EMPTY_WORD, EIGHT + ONE, ONE).unwrap();
+    assert_eq!(2, decoder.block_stack.depth());
+
+    decoder.end_control_block(EMPTY_WORD).unwrap();
+    decoder.end_control_block(EMPTY_WORD).unwrap();
+    assert_eq!(0, decoder.block_stack.depth());
+}
+
+#[test]
+fn block_info_ordered_by_address() {
+    let mut block_stack = BlockStack::new(ExecutionOptions::DEFAULT_MAX_BLOCK_NESTING_DEPTH);
+    block_stack.push(EIGHT, BlockType::Join(false), None).unwrap();
+    block_stack.push(Felt::new(24), BlockType::Span, None).unwrap();
+    let span1 = block_stack.pop().unwrap();
+    block_stack.push(Felt::new(16), BlockType::Span, None).unwrap();
+    let span2 = block_stack.pop().unwrap();
+    let join = block_stack.pop().unwrap();
+
+    // a block with the same address and parent as an existing one is a duplicate
+    block_stack.push(EIGHT, BlockType::Join(false), None).unwrap();
+    block_stack.push(Felt::new(16), BlockType::Split, None).unwrap();
+    let duplicate = block_stack.pop().unwrap();
+
+    let blocks = BTreeSet::from([span1, duplicate, join, span2]);
+    let addrs = blocks.iter().map(|block| block.addr).collect::<Vec<_>>();
+    assert_eq!(vec![EIGHT, Felt::new(16), Felt::new(24)], addrs);
+}
+
+#[test]
+fn validate_well_formed() {
+    let program = {
+        let mut mast_forest = MastForest::new();
+
+        let basic_block_id = mast_forest.add_block(vec![Operation::Pad], None).unwrap();
+        let join_node_id = mast_forest.add_join(basic_block_id, basic_block_id).unwrap();
+        mast_forest.make_root(join_node_id);
+
+        Program::new(mast_forest.into(), join_node_id)
+    };
+    build_decoder(&program).validate_well_formed().unwrap();
+
+    // a JOIN block which was never ended
+    let basic_block = BasicBlockNode::new(vec![Operation::Pad], None).unwrap();
+    let mut decoder = Decoder::default();
+    decoder.start_join(EMPTY_WORD, EMPTY_WORD, INIT_ADDR).unwrap();
+    decoder.start_basic_block(&basic_block.op_batches()[0], ONE, EIGHT).unwrap();
+    assert_matches!(decoder.validate_well_formed(), Err(DecoderError::UnfinishedSpanBlock));
+
+    decoder.execute_user_op(Operation::Pad, 0).unwrap();
+    decoder.end_basic_block(basic_block.digest().into()).unwrap();
+    assert_matches!(decoder.validate_well_formed(), Err(DecoderError::UnfinishedCodeBlocks(1)));
+
+    decoder.end_control_block(EMPTY_WORD).unwrap();
+    decoder.validate_well_formed().unwrap();
+}
+
+#[test]
+fn verify() {
+    let program = {
+        let mut mast_forest = MastForest::new();
+
+        let basic_block1_id = mast_forest.add_block(vec![Operation::Mul], None).unwrap();
+        let basic_block2_id = mast_forest.add_block(vec![Operation::Add], None).unwrap();
+        let join_node_id = mast_forest.add_join(basic_block1_id, basic_block2_id).unwrap();
+        mast_forest.make_root(join_node_id);
+
+        Program::new(mast_forest.into(), join_node_id)
+    };
+    let decoder = build_decoder(&program);
+    decoder.verify(program.hash()).unwrap();
+
+    // a different program hash
+    let other_hash = Digest::new([ONE; 4]);
+    assert_matches!(
+        decoder.verify(other_hash),
+        Err(DecoderError::ProgramHashMismatch { expected, actual })
+            if expected == other_hash && actual == program.hash()
+    );
+
+    // decoding which was not completed fails before the hash is checked
+    let mut decoder = Decoder::default();
+    decoder.start_join(EMPTY_WORD, EMPTY_WORD, INIT_ADDR).unwrap();
+    assert_matches!(decoder.verify(program.hash()), Err(DecoderError::UnfinishedCodeBlocks(1)));
+}
+
+#[test]
+fn max_nesting_depth() {
+    let mut decoder = Decoder::default().with_max_nesting_depth(3);
+
+    // reaching the limit is allowed
+    for i in 0..3 {
+        decoder.start_join(EMPTY_WORD, EMPTY_WORD, Felt::new(i * 8 + 1)).unwrap();
+    }
+
+    // exceeding the limit is not
+    assert_matches!(
+        decoder.start_join(EMPTY_WORD, EMPTY_WORD, Felt::new(25)),
+        Err(DecoderError::MaxNestingExceeded(3))
+    );
+
+    // once a block is ended, a new one can be started again
+    decoder.end_control_block(EMPTY_WORD).unwrap();
+    decoder.start_join(EMPTY_WORD, EMPTY_WORD, Felt::new(25)).unwrap();
+
+    // the limit is set via execution options; the basic blocks of the program below are nested
+    // 3 blocks deep
+    let program = {
+        let mut mast_forest = MastForest::new();
+
+        let basic_block_1_id = mast_forest.add_block(vec![Operation::Pad], None).unwrap();
+        let basic_block_2_id = mast_forest.add_block(vec![Operation::Drop], None).unwrap();
+        let join_id = mast_forest.add_join(basic_block_1_id, basic_block_2_id).unwrap();
+        let loop_node_id = mast_forest.add_loop(join_id).unwrap();
+        mast_forest.make_root(loop_node_id);
+
+        Program::new(mast_forest.into(), loop_node_id)
+    };
+    let stack_inputs = StackInputs::try_from_ints([0, 1]).unwrap();
+
+    let options = ExecutionOptions::default().with_max_block_nesting_depth(2);
+    let mut process = Process::new(Kernel::default(), stack_inputs.clone(), options);
+    assert_matches!(
+        process.execute(&program, &mut DefaultHost::default()),
+        Err(ExecutionError::DecoderError(DecoderError::MaxNestingExceeded(2)))
+    );
+
+    let options = ExecutionOptions::default().with_max_block_nesting_depth(3);
+    let mut process = Process::new(Kernel::default(), stack_inputs, options);
+    process.execute(&program, &mut DefaultHost::default()).unwrap();
+}
+
+#[test]
+fn decoder_errors() {
+    let basic_block = BasicBlockNode::new(vec![Operation::Pad], None).unwrap();
+    let mut decoder = Decoder::default();
+
+    // there are no blocks to end
+    assert_matches!(decoder.end_control_block(EMPTY_WORD), Err(DecoderError::EmptyBlockStack));
+
+    // user operations and batches can be decoded only within a SPAN block
+    decoder.start_join(EMPTY_WORD, EMPTY_WORD, INIT_ADDR).unwrap();
+    assert_matches!(decoder.execute_user_op(Operation::Pad, 0), Err(DecoderError::NotInSpan));
+    assert_matches!(decoder.respan(&basic_block.op_batches()[0]), Err(DecoderError::NotInSpan));
+    assert_matches!(decoder.end_basic_block(EMPTY_WORD), Err(DecoderError::NotInSpan));
+
+    // decoder errors are surfaced as execution errors
+    assert_matches!(
+        ExecutionError::from(DecoderError::NotInSpan),
+        ExecutionError::DecoderError(DecoderError::NotInSpan)
+    );
+}
+
+// DEBUG INFO TESTS
+// ================================================================================================
+
+#[test]
+fn debug_info_block_op_counts() {
+    let basic_block1 = MastNode::new_basic_block(vec![Operation::Mul], None).unwrap();
+    let basic_block2 =
+        MastNode::new_basic_block(vec![Operation::Pad, Operation::Add, Operation::Drop], None)
+            .unwrap();
+    let program = {
+        let mut mast_forest = MastForest::new();
+
+        let basic_block1_id = mast_forest.add_node(basic_block1).unwrap();
+        let basic_block2_id = mast_forest.add_node(basic_block2).unwrap();
+
+        let join_node_id = mast_forest.add_join(basic_block1_id, basic_block2_id).unwrap();
+        mast_forest.make_root(join_node_id);
+
+        Program::new(mast_forest.into(), join_node_id)
+    };
+
+    let mut host = DefaultHost::default();
+    let mut process = Process::new_debug(Kernel::default(), StackInputs::default());
+    process.execute(&program, &mut host).unwrap();
+    let (_, decoder, ..) = process.into_parts();
+
+    // user operations are attributed to the SPAN blocks nested inside the JOIN block
+    let span1_addr = INIT_ADDR + EIGHT;
+    let span2_addr = INIT_ADDR + Felt::new(16);
+    let counts = decoder.debug_info().block_op_counts();
+    assert_eq!(2, counts.len());
+    assert_eq!(Some(&1), counts.get(&span1_addr.as_int()));
+    assert_eq!(Some(&3), counts.get(&span2_addr.as_int()));
+}
+
+#[test]
+fn debug_info_block_op_counts_with_respan() {
+    // each PUSH operation takes up an extra op group for its immediate value, and thus, the ops
+    // do not fit into a single batch
+    let mut ops = vec![Operation::Push(ONE); 9];
+    ops.extend([Operation::Drop; 9]);
+    let basic_block = BasicBlockNode::new(ops.clone(), None).unwrap();
+    assert!(basic_block.op_batches().len() > 1);
+    let program = {
+        let mut mast_forest = MastForest::new();
+
+        let basic_block_id = mast_forest.add_node(MastNode::Block(basic_block)).unwrap();
+        mast_forest.make_root(basic_block_id);
+
+        Program::new(mast_forest.into(), basic_block_id)
+    };
+
+    let mut host = DefaultHost::default();
+    let mut process = Process::new_debug(Kernel::default(), StackInputs::default());
+    process.execute(&program, &mut host).unwrap();
+    let (_, decoder, ..) = process.into_parts();
+
+    // operations from all batches are attributed to the address of the SPAN row
+    let counts = decoder.debug_info().block_op_counts();
+    assert_eq!(1, counts.len());
+    assert_eq!(Some(&ops.len()), counts.get(&INIT_ADDR.as_int()));
+}
+
+#[test]
+fn debug_info_split_branches() {
+    let basic_block1 = MastNode::new_basic_block(vec![Operation::Mul], None).unwrap();
+    let basic_block2 = MastNode::new_basic_block(vec![Operation::Add], None).unwrap();
+    let basic_block3 = MastNode::new_basic_block(vec![Operation::Pad], None).unwrap();
+    let basic_block4 = MastNode::new_basic_block(vec![Operation::Drop], None).unwrap();
+    let program = {
+        let mut mast_forest = MastForest::new();
+
+        let basic_block1_id = mast_forest.add_node(basic_block1).unwrap();
+        let basic_block2_id = mast_forest.add_node(basic_block2).unwrap();
+        let basic_block3_id = mast_forest.add_node(basic_block3).unwrap();
+        let basic_block4_id = mast_forest.add_node(basic_block4).unwrap();
+
+        // the first SPLIT consumes the ONE provided as input, and the second one consumes the
+        // ZERO pushed onto the stack by the PAD operation
+        let split1_id = mast_forest.add_split(basic_block1_id, basic_block2_id).unwrap();
+        let split2_id = mast_forest.add_split(basic_block2_id, basic_block4_id).unwrap();
+        let inner_join_id = mast_forest.add_join(basic_block3_id, split2_id).unwrap();
+        let join_id = mast_forest.add_join(split1_id, inner_join_id).unwrap();
+        mast_forest.make_root(join_id);
+
+        Program::new(mast_forest.into(), join_id)
+    };
+
+    let mut host = DefaultHost::default();
+    let stack_inputs = StackInputs::try_from_ints([1]).unwrap();
+    let mut process = Process::new_debug(Kernel::default(), stack_inputs);
+    process.execute(&program, &mut host).unwrap();
+    let (_, decoder, ..) = process.into_parts();
+
+    let branches = decoder.debug_info().split_branches();
+    assert_eq!(2, branches.len());
+    assert_eq!((INIT_ADDR + EIGHT, true), branches[0]);
+    assert!(!branches[1].1);
+
+    // a SPLIT with a non-binary condition is not recorded
+    let stack_inputs = StackInputs::try_from_ints([2]).unwrap();
+    let mut process = Process::new_debug(Kernel::default(), stack_inputs);
+    assert!(process.execute(&program, &mut host).is_err());
+    let (_, decoder, ..) = process.into_parts();
+    assert!(decoder.debug_info().split_branches().is_empty());
+}
+
+#[test]
+fn debug_info_total_cost() {
+    let basic_block =
+        MastNode::new_basic_block(vec![Operation::Pad, Operation::Mul], None).unwrap();
+    let program = {
+        let mut mast_forest = MastForest::new();
+
+        let basic_block_id = mast_forest.add_node(basic_block).unwrap();
+        let join_node_id = mast_forest.add_join(basic_block_id, basic_block_id).unwrap();
+        mast_forest.make_root(join_node_id);
+
+        Program::new(mast_forest.into(), join_node_id)
+    };
+
+    let mut host = DefaultHost::default();
+    let mut process = Process::new_debug(Kernel::default(), StackInputs::default());
+    process.execute(&program, &mut host).unwrap();
+    let (_, decoder, ..) = process.into_parts();
+
+    // JOIN, 2 x (SPAN, PAD, MUL, END), END
+    let debug_info = decoder.debug_info();
+    assert_eq!(10, debug_info.total_cost(&BTreeMap::new()));
+
+    let op_costs = BTreeMap::from([
+        (Operation::Mul.op_code(), 4),
+        (Operation::Span.op_code(), 0),
+        (Operation::End.op_code(), 0),
+    ]);
+    assert_eq!(1 + 2 * (1 + 4), debug_info.total_cost(&op_costs));
+}
+
+#[test]
+fn max_op_group_value() {
+    // the first op group is fully packed, and the second one holds a single operation
+    let mut ops = vec![Operation::Mul; OP_GROUP_SIZE];
+    ops.push(Operation::Add);
+    let program = {
+        let mut mast_forest = MastForest::new();
+
+        let basic_block_id = mast_forest.add_block(ops.clone(), None).unwrap();
+        mast_forest.make_root(basic_block_id);
+
+        Program::new(mast_forest.into(), basic_block_id)
+    };
+
+    let group1 = build_op_group(&ops[..OP_GROUP_SIZE]);
+    let group2 = build_op_group(&ops[OP_GROUP_SIZE..]);
+    let expected = if group1.as_int() > group2.as_int() {
+        group1
+    } else {
+        group2
+    };
+    assert_eq!(expected, build_decoder(&program).max_op_group_value());
+}
+
+#[test]
+fn op_log_replay() {
+    let basic_block1 =
+        MastNode::new_basic_block(vec![Operation::Pad, Operation::Drop], None).unwrap();
+    let basic_block2 =
+        MastNode::new_basic_block(vec![Operation::Push(EIGHT), Operation::Drop], None).unwrap();
+    let basic_block3 = MastNode::new_basic_block(vec![Operation::Add], None).unwrap();
+    let program = {
+        let mut mast_forest = MastForest::new();
+
+        let basic_block1_id = mast_forest.add_node(basic_block1).unwrap();
+        let basic_block2_id = mast_forest.add_node(basic_block2).unwrap();
+        let basic_block3_id = mast_forest.add_node(basic_block3).unwrap();
+
+        let loop_id = mast_forest.add_loop(basic_block1_id).unwrap();
+        let split_id = mast_forest.add_split(basic_block2_id, basic_block3_id).unwrap();
+        let join_id = mast_forest.add_join(loop_id, split_id).unwrap();
+        mast_forest.make_root(join_id);
+
+        Program::new(mast_forest.into(), join_id)
+    };
+
+    // the loop body is executed twice, and then the first branch of the SPLIT is taken
+    let mut host = DefaultHost::default();
+    let stack_inputs = StackInputs::try_from_ints([1, 0, 1, 1]).unwrap();
+    let options = ExecutionOptions::default().with_decoder_op_log();
+    let mut process = Process::new(Kernel::default(), stack_inputs.clone(), options);
+    process.execute(&program, &mut host).unwrap();
+    let (_, decoder, ..) = process.into_parts();
+
+    let replayed = Decoder::replay(decoder.op_log().unwrap()).unwrap();
+    assert_eq!(decoder.trace_len(), replayed.trace_len());
+
+    let trace = decoder.into_trace(MIN_TRACE_LEN, NUM_RAND_ROWS);
+    let replayed_trace = replayed.into_trace(MIN_TRACE_LEN, NUM_RAND_ROWS);
+    assert_eq!(None, trace.first_divergence(&replayed_trace));
+
+    // calls are not recorded unless explicitly requested, even in debug mode
+    let mut process = Process::new_debug(Kernel::default(), stack_inputs);
+    process.execute(&program, &mut host).unwrap();
+    let (_, decoder, ..) = process.into_parts();
+    assert!(decoder.op_log().is_none());
+}
+
+// TRACE GENERATION TESTS
+// ================================================================================================
+
+#[test]
+fn trace_constants_match_canonical_values() {
+    assert_eq!(MIN_TRACE_LEN, ExecutionTrace::MIN_TRACE_LEN);
+    assert_eq!(Felt::new(hasher::HASH_CYCLE_LEN as u64), HASH_CYCLE_LEN);
+
+    // each op batch is absorbed into the rate portion of the hasher state in a single permutation
+    assert_eq!(hasher::RATE_LEN, NUM_HASHER_COLUMNS);
+    assert_eq!(hasher::RATE_LEN, OP_BATCH_SIZE);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn into_trace_profiled() {
+    let program = build_pad_add_program();
+
+    let expected = build_decoder(&program).into_trace(MIN_TRACE_LEN, NUM_RAND_ROWS);
+    let (trace, profile) =
+        build_decoder(&program).into_trace_profiled(MIN_TRACE_LEN, NUM_RAND_ROWS);
+
+    // profiling must not affect the generated trace
+    assert_eq!(expected.trace, trace.trace);
+    assert_eq!(Duration::ZERO, profile.transposing);
+
+    let expected = build_decoder(&program).into_row_major(MIN_TRACE_LEN, NUM_RAND_ROWS);
+    let (rows, _) = build_decoder(&program).into_row_major_profiled(MIN_TRACE_LEN, NUM_RAND_ROWS);
+    assert_eq!(expected, rows);
+}
+
+#[test]
+fn into_matrix() {
+    let program = build_pad_add_program();
+
+    let expected = build_decoder(&program).into_trace(MIN_TRACE_LEN, NUM_RAND_ROWS);
+    let matrix = build_decoder(&program).into_matrix(MIN_TRACE_LEN, NUM_RAND_ROWS);
+
+    assert_eq!(DECODER_TRACE_WIDTH, matrix.num_cols());
+    assert_eq!(MIN_TRACE_LEN, matrix.num_rows());
+    for (i, column) in expected.trace.iter().enumerate() {
+        assert_eq!(column, matrix.get_column(i));
+    }
+}
+
+#[test]
+fn into_row_major() {
+    let program = build_pad_add_program();
+
+    let expected = build_decoder(&program).into_trace(MIN_TRACE_LEN, NUM_RAND_ROWS);
+    let rows = build_decoder(&program).into_row_major(MIN_TRACE_LEN, NUM_RAND_ROWS);
+
+    assert_eq!(MIN_TRACE_LEN, rows.len());
+    for (i, row) in rows.iter().enumerate() {
+        assert_eq!(DECODER_TRACE_WIDTH, row.len());
+        for (j, column) in expected.trace.iter().enumerate() {
+            assert_eq!(column[i], row[j]);
+        }
+    }
+}
+
+#[test]
+fn column_commitments() {
+    let program = build_pad_add_program();
+
+    let trace = build_decoder(&program).into_trace(MIN_TRACE_LEN, NUM_RAND_ROWS);
+    let commitments = trace.column_commitments::<Rpo256>();
+    assert_eq!(DECODER_TRACE_WIDTH, commitments.len());
+    assert_eq!(Rpo256::hash_elements(&trace.trace[ADDR_COL_IDX]), commitments[ADDR_COL_IDX]);
+
+    // commitments are deterministic
+    let other = build_decoder(&program).into_trace(MIN_TRACE_LEN, NUM_RAND_ROWS);
+    assert_eq!(commitments, other.column_commitments::<Rpo256>());
+}
+
+#[test]
+fn rebase_addresses() {
+    // the second basic block spans two batches, and thus, is continued by a RESPAN
+    let basic_block1 = MastNode::new_basic_block(vec![Operation::Pad], None).unwrap();
+    let basic_block2 =
+        MastNode::new_basic_block(vec![Operation::Push(ONE); OP_BATCH_SIZE], None).unwrap();
+    let program = {
+        let mut mast_forest = MastForest::new();
+
+        let basic_block1_id = mast_forest.add_node(basic_block1).unwrap();
+        let basic_block2_id = mast_forest.add_node(basic_block2).unwrap();
+        let join_node_id = mast_forest.add_join(basic_block1_id, basic_block2_id).unwrap();
+        mast_forest.make_root(join_node_id);
+
+        Program::new(mast_forest.into(), join_node_id)
+    };
+
+    let expected = build_decoder(&program).into_trace(MIN_TRACE_LEN, NUM_RAND_ROWS);
+    let mut trace = build_decoder(&program).into_trace(MIN_TRACE_LEN, NUM_RAND_ROWS);
+    let new_base = Felt::new(1000);
+    trace.rebase_addresses(new_base);
+    let delta = new_base - INIT_ADDR;
+
+    let parent_addr_col_idx = HASHER_STATE_RANGE.start + 1;
+    for row in 0..MIN_TRACE_LEN {
+        let addr = expected.trace[ADDR_COL_IDX][row];
+        let expected_addr = if addr == ZERO { ZERO } else { addr + delta };
+        assert_eq!(expected_addr, trace.trace[ADDR_COL_IDX][row]);
+
+        let parent_addr = expected.trace[parent_addr_col_idx][row];
+        if expected.trace[IN_SPAN_COL_IDX][row] == ONE && parent_addr != ZERO {
+            assert_eq!(parent_addr + delta, trace.trace[parent_addr_col_idx][row]);
+        } else {
+            assert_eq!(parent_addr, trace.trace[parent_addr_col_idx][row]);
+        }
+    }
+
+    // the END row of the root block holds the new base address
+    let ops = trace.to_operations().unwrap();
+    assert_eq!(new_base, trace.trace[ADDR_COL_IDX][ops.len() - 1]);
+}
+
+#[test]
+fn first_divergence() {
+    let program = build_pad_add_program();
+
+    let expected = build_decoder(&program).into_trace(MIN_TRACE_LEN, NUM_RAND_ROWS);
+    let mut trace = build_decoder(&program).into_trace(MIN_TRACE_LEN, NUM_RAND_ROWS);
+    assert_eq!(None, expected.first_divergence(&trace));
+
+    // the earliest row is reported first, regardless of the column
+    trace.trace[ADDR_COL_IDX][7] += ONE;
+    trace.trace[IN_SPAN_COL_IDX][3] += ONE;
+    assert_eq!(Some((3, IN_SPAN_COL_IDX)), expected.first_divergence(&trace));
+
+    // within a row, the leftmost column is reported first
+    trace.trace[ADDR_COL_IDX][3] += ONE;
+    assert_eq!(Some((3, ADDR_COL_IDX)), expected.first_divergence(&trace));
+
+    // if one trace is a prefix of the other, the first row past the shorter trace is reported
+    let mut trace = build_decoder(&program).into_trace(MIN_TRACE_LEN, NUM_RAND_ROWS);
+    trace.trace.iter_mut().for_each(|column| column.truncate(5));
+    assert_eq!(Some((5, 0)), expected.first_divergence(&trace));
+    assert_eq!(Some((5, 0)), trace.first_divergence(&expected));
+}
+
+#[test]
+fn diff_report() {
+    let program = build_pad_add_program();
+
+    let expected = build_decoder(&program).into_trace(MIN_TRACE_LEN, NUM_RAND_ROWS);
+    let mut trace = build_decoder(&program).into_trace(MIN_TRACE_LEN, NUM_RAND_ROWS);
+    assert_eq!("", expected.diff_report(&trace, 10));
+
+    // every differing row is reported once, regardless of the number of differing columns
+    trace.trace[ADDR_COL_IDX][2] += ONE;
+    trace.trace[IN_SPAN_COL_IDX][2] += ONE;
+    trace.trace[ADDR_COL_IDX][5] += ONE;
+    let report = expected.diff_report(&trace, 10);
+    assert!(report.starts_with("--- self\n+++ other\n"));
+    assert!(report.contains("@@ row 2 @@"));
+    assert!(report.contains("@@ row 5 @@"));
+    assert_eq!(2, report.matches("@@ row").count());
+    assert_eq!(
+        4,
+        report
+            .lines()
+            .filter(|line| line.starts_with("- ") || line.starts_with("+ "))
+            .count()
+    );
+
+    // rows beyond the limit are only counted
+    let report = expected.diff_report(&trace, 1);
+    assert!(report.contains("@@ row 2 @@"));
+    assert!(!report.contains("@@ row 5 @@"));
+    assert!(report.ends_with("... 1 more differing rows\n"));
+
+    // extra rows of a longer trace are reported with only one version
+    let longer = build_decoder(&program).into_trace(2 * MIN_TRACE_LEN, NUM_RAND_ROWS);
+    let report = expected.diff_report(&longer, usize::MAX);
+    assert_eq!(MIN_TRACE_LEN, report.matches("@@ row").count());
+    assert!(report.contains(&format!("@@ row {MIN_TRACE_LEN} @@\n+ ")));
+}
+
+#[test]
+fn to_operations() {
+    let ops = vec![Operation::Pad, Operation::Push(Felt::new(7)), Operation::Add, Operation::Drop];
+    let program = {
+        let mut mast_forest = MastForest::new();
+
+        let basic_block_id = mast_forest.add_block(ops.clone(), None).unwrap();
+        mast_forest.make_root(basic_block_id);
+
+        Program::new(mast_forest.into(), basic_block_id)
+    };
+
+    let trace = build_decoder(&program).into_trace(MIN_TRACE_LEN, NUM_RAND_ROWS);
+    let operations = trace.to_operations().unwrap();
+
+    // the immediate value of PUSH is recovered from the op batch rather than decoded as an opcode
+    let mut expected = vec![Operation::Span];
+    expected.extend_from_slice(&ops);
+    expected.push(Operation::End);
+    assert_eq!(expected, operations);
+
+    // re-decoding the recovered user operations produces the same trace
+    let user_ops = operations[1..operations.len() - 1].to_vec();
+    let program = {
+        let mut mast_forest = MastForest::new();
+
+        let basic_block_id = mast_forest.add_block(user_ops, None).unwrap();
+        mast_forest.make_root(basic_block_id);
+
+        Program::new(mast_forest.into(), basic_block_id)
+    };
+    let redecoded = build_decoder(&program).into_trace(MIN_TRACE_LEN, NUM_RAND_ROWS);
+    assert_eq!(None, trace.first_divergence(&redecoded));
+}
+
+#[test]
+fn to_operations_tampered_trace() {
+    let ops = vec![Operation::Pad, Operation::Push(Felt::new(7)), Operation::Add, Operation::Drop];
+    let program = {
+        let mut mast_forest = MastForest::new();
+
+        let basic_block_id = mast_forest.add_block(ops, None).unwrap();
+        mast_forest.make_root(basic_block_id);
+
+        Program::new(mast_forest.into(), basic_block_id)
+    };
+    let trace = build_decoder(&program).into_trace(MIN_TRACE_LEN, NUM_RAND_ROWS);
+
+    // the group count of the PUSH row exceeds the number of groups in the SPAN
+    let mut tampered = build_decoder(&program).into_trace(MIN_TRACE_LEN, NUM_RAND_ROWS);
+    tampered.trace[GROUP_COUNT_COL_IDX][2] = Felt::new(100);
+    assert_matches!(tampered.to_operations(), Err(DecoderError::InvalidTraceStructure(2)));
+    assert_matches!(tampered.to_program(), Err(DecoderError::InvalidTraceStructure(2)));
+
+    // the immediate value would be located past the end of the batch
+    let mut tampered = build_decoder(&program).into_trace(MIN_TRACE_LEN, NUM_RAND_ROWS);
+    tampered.trace[GROUP_COUNT_COL_IDX][0] = Felt::new(100);
+    assert_matches!(tampered.to_operations(), Err(DecoderError::InvalidTraceStructure(2)));
+
+    // the PUSH operation is executed before any SPAN block is started
+    let mut tampered = trace;
+    let push_op_code = Operation::Push(ZERO).op_code();
+    for i in 0..NUM_OP_BITS {
+        let bit = (push_op_code >> i) & 1;
+        tampered.trace[OP_BITS_OFFSET + i][0] = Felt::new(bit as u64);
+    }
+    assert_matches!(tampered.to_operations(), Err(DecoderError::InvalidTraceStructure(0)));
+}
+
+#[test]
+fn op_code_decoding() {
+    // every opcode is mapped to an operation with the same opcode
+    for op_code in 0..=u8::MAX {
+        if let Some(op) = operation_from_op_code(op_code) {
+            assert_eq!(op_code, op.op_code());
+        }
+    }
+
+    // associated data cannot be recovered from an opcode, and thus, is set to 0
+    let ops = [
+        Operation::Join,
+        Operation::Span,
+        Operation::End,
+        Operation::Halt,
+        Operation::Assert(0),
+        Operation::Push(ZERO),
+        Operation::Emit(0),
+    ];
+    for op in ops {
+        assert_eq!(Some(op), operation_from_op_code(op.op_code()));
+    }
+    assert_eq!(
+        Some(Operation::Push(ZERO)),
+        operation_from_op_code(Operation::Push(ONE).op_code())
+    );
+}
+
+#[test]
+fn lde_size() {
+    let basic_block = MastNode::new_basic_block(vec![Operation::Pad], None).unwrap();
+    let program = {
+        let mut mast_forest = MastForest::new();
+
+        let basic_block_id = mast_forest.add_node(basic_block).unwrap();
+        mast_forest.make_root(basic_block_id);
+
+        Program::new(mast_forest.into(), basic_block_id)
+    };
+
+    let trace = build_decoder(&program).into_trace(MIN_TRACE_LEN, NUM_RAND_ROWS);
+    assert_eq!(DECODER_TRACE_WIDTH * MIN_TRACE_LEN * 8, trace.lde_size(8));
+    assert_eq!(DECODER_TRACE_WIDTH * MIN_TRACE_LEN * 8 * 8, trace.lde_size_in_bytes(8));
+}
+
+#[test]
+fn to_program() {
+    let program = {
+        let mut mast_forest = MastForest::new();
+
+        let basic_block1_id = mast_forest.add_block(vec![Operation::Mul], None).unwrap();
+        let basic_block2_id = mast_forest.add_block(vec![Operation::Add], None).unwrap();
+        let basic_block3_id = mast_forest.add_block(vec![Operation::Pad], None).unwrap();
+        let split_node_id = mast_forest.add_split(basic_block1_id, basic_block2_id).unwrap();
+        let loop_node_id = mast_forest.add_loop(basic_block3_id).unwrap();
+        let call_node_id = mast_forest.add_call(basic_block2_id).unwrap();
+        let join_node1_id = mast_forest.add_join(split_node_id, loop_node_id).unwrap();
+        let join_node2_id = mast_forest.add_join(join_node1_id, call_node_id).unwrap();
+        mast_forest.make_root(join_node2_id);
+
+        Program::new(mast_forest.into(), join_node2_id)
+    };
+
+    // the SPLIT block takes the TRUE branch, and the loop body is executed once
+    let stack_inputs = StackInputs::try_from_ints([1, 1, 1]).unwrap();
+    let mut process = Process::new(Kernel::default(), stack_inputs, ExecutionOptions::default());
+    process.execute(&program, &mut DefaultHost::default()).unwrap();
+    let (_, decoder, ..) = process.into_parts();
+    let mut trace = decoder.into_trace(MIN_TRACE_LEN, NUM_RAND_ROWS);
+
+    // the branch which was not taken is replaced with an external node
+    let reconstructed = trace.to_program().unwrap();
+    assert_eq!(program.hash(), reconstructed.hash());
+    let external_nodes = reconstructed
+        .mast_forest()
+        .nodes()
+        .iter()
+        .filter(|node| matches!(node, MastNode::External(_)))
+        .count();
+    assert_eq!(1, external_nodes);
+
+    // a tampered block hash is detected at the END row of the block
+    let root_end_row = trace.to_operations().unwrap().len() - 1;
+    trace.trace[HASHER_STATE_RANGE.start][root_end_row] += ONE;
+    assert_matches!(trace.to_program(), Err(DecoderError::BlockHashMismatch(row)) if row == root_end_row);
+}
+
+#[test]
+fn find_redundant_ops() {
+    let ops = vec![
+        Operation::Pad,
+        Operation::Swap,
+        Operation::Swap,
+        Operation::Dup0,
+        Operation::Drop,
+        Operation::Drop,
+    ];
+    let program = {
+        let mut mast_forest = MastForest::new();
+
+        let basic_block_id = mast_forest.add_block(ops, None).unwrap();
+        mast_forest.make_root(basic_block_id);
+
+        Program::new(mast_forest.into(), basic_block_id)
+    };
+
+    // rows are offset by one because of the SPAN row
+    let trace = build_decoder(&program).into_trace(MIN_TRACE_LEN, NUM_RAND_ROWS);
+    assert_eq!(
+        vec![(2, RedundancyKind::DoubleSwap), (4, RedundancyKind::PushDrop)],
+        trace.find_redundant_ops().unwrap()
+    );
+
+    // a pair of NOT operations is not redundant because NOT fails on non-binary operands
+    let ops = vec![
+        Operation::Pad,
+        Operation::Incr,
+        Operation::Not,
+        Operation::Not,
+        Operation::Neg,
+        Operation::Neg,
+        Operation::Drop,
+    ];
+    let program = {
+        let mut mast_forest = MastForest::new();
+
+        let basic_block_id = mast_forest.add_block(ops, None).unwrap();
+        mast_forest.make_root(basic_block_id);
+
+        Program::new(mast_forest.into(), basic_block_id)
+    };
+
+    let trace = build_decoder(&program).into_trace(MIN_TRACE_LEN, NUM_RAND_ROWS);
+    assert_eq!(vec![(5, RedundancyKind::DoubleNegation)], trace.find_redundant_ops().unwrap());
+}
+
+#[test]
+fn padding_rows_for() {
+    let program = build_pad_add_program();
+    let decoder = build_decoder(&program);
+    let num_real_rows = decoder.trace_len();
+
+    // the predicted number of rows matches the number of HALT rows in the padded trace
+    let num_padding_rows = decoder.padding_rows_for(MIN_TRACE_LEN, NUM_RAND_ROWS).unwrap();
+    let trace = decoder.into_trace(MIN_TRACE_LEN, NUM_RAND_ROWS);
+    let num_halt_rows = MIN_TRACE_LEN - trace.to_operations().unwrap().len();
+    assert_eq!(num_halt_rows, num_padding_rows);
+    assert_eq!(MIN_TRACE_LEN - num_real_rows, num_padding_rows);
+
+    // the trace together with the random rows must fit into the target length
+    let decoder = build_decoder(&program);
+    let target_len = num_real_rows + NUM_RAND_ROWS;
+    assert_eq!(NUM_RAND_ROWS, decoder.padding_rows_for(target_len, NUM_RAND_ROWS).unwrap());
+    assert_matches!(
+        decoder.padding_rows_for(num_real_rows, NUM_RAND_ROWS),
+        Err(DecoderError::TraceLenTooSmall { trace_len, min_len })
+            if trace_len == num_real_rows && min_len == target_len
+    );
+}
+
+#[test]
+fn op_transition_counts() {
+    let basic_block = MastNode::new_basic_block(
+        vec![Operation::Pad, Operation::Pad, Operation::Add, Operation::Pad, Operation::Add],
+        None,
+    )
+    .unwrap();
+    let program = {
+        let mut mast_forest = MastForest::new();
+
+        let basic_block_id = mast_forest.add_node(basic_block).unwrap();
+        let join_node_id = mast_forest.add_join(basic_block_id, basic_block_id).unwrap();
+        mast_forest.make_root(join_node_id);
+
+        Program::new(mast_forest.into(), join_node_id)
+    };
+
+    let trace = build_decoder(&program).into_trace(MIN_TRACE_LEN, NUM_RAND_ROWS);
+    let counts = trace.op_transition_counts();
+
+    // transitions between the two basic blocks are not counted
+    let pad = Operation::Pad.op_code();
+    let add = Operation::Add.op_code();
+    assert_eq!(Some(&2), counts.get(&(pad, pad)));
+    assert_eq!(Some(&4), counts.get(&(pad, add)));
+    assert_eq!(Some(&2), counts.get(&(add, pad)));
+    assert_eq!(None, counts.get(&(add, add)));
+    assert_eq!(8, counts.values().sum::<usize>());
+}
+
+#[test]
+fn estimate_memory() {
+    let basic_block = BasicBlockNode::new(vec![Operation::Noop], None).unwrap();
+    let mut decoder = Decoder::default();
+    decoder.start_basic_block(&basic_block.op_batches()[0], ONE, INIT_ADDR).unwrap();
+    let initial_estimate = decoder.estimate_memory();
+    assert!(initial_estimate >= DECODER_TRACE_WIDTH * MIN_TRACE_LEN * size_of::<Felt>());
+
+    // decoding more rows than the initially allocated trace length requires more memory
+    for _ in 0..MIN_TRACE_LEN {
+        decoder.execute_user_op(Operation::Noop, 0).unwrap();
+    }
+    assert!(decoder.estimate_memory() > initial_estimate);
+}
+
 // HELPER REGISTERS TESTS
 // ================================================================================================
 #[test]
@@ -1476,6 +2617,25 @@ fn build_trace(stack_inputs: &[u64], program: &Program) -> (DecoderTrace, usize)
     )
 }
 
+fn build_decoder(program: &Program) -> Decoder {
+    let mut host = DefaultHost::default();
+    let mut process =
+        Process::new(Kernel::default(), StackInputs::default(), ExecutionOptions::default());
+    process.execute(program, &mut host).unwrap();
+
+    let (_, decoder, ..) = process.into_parts();
+    decoder
+}
+
+fn build_pad_add_program() -> Program {
+    let mut mast_forest = MastForest::new();
+
+    let basic_block_id = mast_forest.add_block(vec![Operation::Pad, Operation::Add], None).unwrap();
+    mast_forest.make_root(basic_block_id);
+
+    Program::new(mast_forest.into(), basic_block_id)
+}
+
 fn build_dyn_trace(stack_inputs: &[u64], program: &Program) -> (DecoderTrace, usize) {
     let stack_inputs = StackInputs::try_from_ints(stack_inputs.iter().copied()).unwrap();
     let mut host = DefaultHost::default();
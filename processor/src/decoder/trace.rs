@@ -81,6 +81,44 @@ impl DecoderTrace {
         result
     }
 
+    /// Returns the number of bytes currently allocated for the columns of this trace.
+    pub fn estimate_memory(&self) -> usize {
+        let num_elements = self.addr_trace.capacity()
+            + self.op_bits_trace.iter().map(Vec::capacity).sum::<usize>()
+            + self.hasher_trace.iter().map(Vec::capacity).sum::<usize>()
+            + self.in_span_trace.capacity()
+            + self.group_count_trace.capacity()
+            + self.op_idx_trace.capacity()
+            + self.op_batch_flag_trace.iter().map(Vec::capacity).sum::<usize>()
+            + self.op_bit_extra_trace.iter().map(Vec::capacity).sum::<usize>();
+
+        num_elements * size_of::<Felt>()
+    }
+
+    /// Returns the opcode of the operation executed at the specified row, or None if the row is
+    /// outside of this trace.
+    pub fn op_code(&self, row: usize) -> Option<u8> {
+        if row >= self.trace_len() {
+            return None;
+        }
+
+        Some(get_op_code(&self.op_bits_trace, row))
+    }
+
+    /// Returns the hash of the loop body recorded at the specified row, or None if the operation
+    /// executed at this row is not a LOOP operation.
+    pub fn loop_body_hash(&self, row: usize) -> Option<Word> {
+        if self.op_code(row)? != Operation::Loop.op_code() {
+            return None;
+        }
+
+        let mut result = [ZERO; DIGEST_LEN];
+        for (i, element) in result.iter_mut().enumerate() {
+            *element = self.hasher_trace[i][row];
+        }
+        Some(result)
+    }
+
     // TRACE MUTATORS
     // --------------------------------------------------------------------------------------------
 
@@ -539,6 +577,15 @@ impl DecoderTrace {
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Returns the opcode of the operation executed at the specified row, given the op bits columns of
+/// a decoder trace.
+pub fn get_op_code(op_bits: &[Vec<Felt>], row: usize) -> u8 {
+    op_bits
+        .iter()
+        .enumerate()
+        .fold(0, |acc, (i, column)| acc | ((column[row].as_int() as u8) << i))
+}
+
 /// Returns op batch flags for the specified group count. If the group count is greater than 8,
 /// we assume that the operation batch is full - i.e., has 8 operation groups.
 fn get_op_batch_flags(num_groups_left: Felt) -> [Felt; NUM_OP_BATCH_FLAGS] {
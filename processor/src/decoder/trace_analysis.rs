@@ -0,0 +1,562 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use miden_air::trace::{
+    decoder::{
+        ADDR_COL_IDX, GROUP_COUNT_COL_IDX, HASHER_STATE_RANGE, IN_SPAN_COL_IDX, OP_BITS_RANGE,
+    },
+    DECODER_TRACE_WIDTH,
+};
+use vm_core::{
+    crypto::hash::ElementHasher,
+    mast::{MastForest, MastNodeId, OP_BATCH_SIZE},
+    utils::{Deserializable, SliceReader},
+    Program,
+};
+
+use super::{trace::get_op_code, DecoderError, Digest, Felt, Operation, ONE, ZERO};
+use crate::DecoderTrace;
+
+// DECODER TRACE ANALYSIS
+// ================================================================================================
+
+impl DecoderTrace {
+    /// Returns the (row, column) coordinates of the first cell at which this trace differs from
+    /// the provided trace, or None if the traces are identical.
+    ///
+    /// Rows are compared in order, and within a row, columns are compared from left to right. If
+    /// the traces have different lengths and the shorter trace matches the beginning of the longer
+    /// one, the first row past the end of the shorter trace is reported together with column 0.
+    pub fn first_divergence(&self, other: &DecoderTrace) -> Option<(usize, usize)> {
+        let self_len = self.trace[0].len();
+        let other_len = other.trace[0].len();
+
+        (0..self_len.max(other_len)).find_map(|row| {
+            if row >= self_len || row >= other_len {
+                return Some((row, 0));
+            }
+
+            (0..DECODER_TRACE_WIDTH)
+                .find(|&col| self.trace[col][row] != other.trace[col][row])
+                .map(|col| (row, col))
+        })
+    }
+
+    /// Returns a unified-diff-like report of the rows at which this trace differs from the
+    /// provided trace.
+    ///
+    /// Each differing row is reported with the values of this trace prefixed by `-` and the
+    /// values of the other trace prefixed by `+`. If one of the traces is longer, its extra rows
+    /// are reported as differing rows with only one version. At most `max_rows` rows are reported;
+    /// the number of omitted rows is noted at the end of the report. If the traces are identical,
+    /// an empty string is returned.
+    pub fn diff_report(&self, other: &DecoderTrace, max_rows: usize) -> String {
+        let self_len = self.trace[0].len();
+        let other_len = other.trace[0].len();
+
+        let differing_rows = (0..self_len.max(other_len))
+            .filter(|&row| {
+                row >= self_len
+                    || row >= other_len
+                    || (0..DECODER_TRACE_WIDTH)
+                        .any(|col| self.trace[col][row] != other.trace[col][row])
+            })
+            .collect::<Vec<_>>();
+        if differing_rows.is_empty() {
+            return String::new();
+        }
+
+        let mut report = String::from("--- self\n+++ other\n");
+        for &row in differing_rows.iter().take(max_rows) {
+            report.push_str(&format!("@@ row {row} @@\n"));
+            if row < self_len {
+                report.push_str(&format!("-{}\n", self.format_row(row)));
+            }
+            if row < other_len {
+                report.push_str(&format!("+{}\n", other.format_row(row)));
+            }
+        }
+
+        let num_omitted = differing_rows.len().saturating_sub(max_rows);
+        if num_omitted > 0 {
+            report.push_str(&format!("... {num_omitted} more differing rows\n"));
+        }
+
+        report
+    }
+
+    /// Returns the number of field elements in the low-degree extension of this trace computed
+    /// with the specified blowup factor.
+    ///
+    /// The length of this trace already includes the rows reserved for random values, and thus,
+    /// the size is computed as `DECODER_TRACE_WIDTH * trace_len * blowup`.
+    ///
+    /// # Panics
+    /// Panics if `blowup` is not a power of two.
+    pub fn lde_size(&self, blowup: usize) -> usize {
+        assert!(blowup.is_power_of_two(), "blowup factor must be a power of two");
+        DECODER_TRACE_WIDTH * self.trace[0].len() * blowup
+    }
+
+    /// Returns the number of bytes in the low-degree extension of this trace computed with the
+    /// specified blowup factor.
+    ///
+    /// # Panics
+    /// Panics if `blowup` is not a power of two.
+    pub fn lde_size_in_bytes(&self, blowup: usize) -> usize {
+        self.lde_size(blowup) * size_of::<Felt>()
+    }
+
+    /// Returns a commitment to each column of this trace, computed by hashing all elements of the
+    /// column with the specified hasher.
+    ///
+    /// Commitments are returned in the order of the columns in the trace.
+    pub fn column_commitments<H: ElementHasher<BaseField = Felt>>(&self) -> Vec<H::Digest> {
+        self.trace.iter().map(|column| H::hash_elements(column)).collect()
+    }
+
+    /// Returns the sequence of operations recorded in this trace.
+    ///
+    /// Control flow operations are included in the order in which they were executed, and padding
+    /// rows at the end of the trace (i.e., the HALT rows) are omitted. Immediate values of PUSH and
+    /// EMIT operations are recovered from the operation groups absorbed by the preceding SPAN or
+    /// RESPAN operation. Error codes of ASSERT, U32ASSERT2, and MPVERIFY operations are not
+    /// recorded in the trace, and thus, are set to 0.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - A row of the trace does not contain a valid opcode.
+    /// - The immediate value of a PUSH or EMIT operation cannot be located, e.g., because the
+    ///   operation is executed outside of a SPAN block, or because the group count recorded in the
+    ///   trace is inconsistent.
+    pub fn to_operations(&self) -> Result<Vec<Operation>, DecoderError> {
+        let mut operations = Vec::new();
+
+        // total number of groups in the current SPAN, index of the first group of the current
+        // batch within the SPAN, and groups of the current batch (None if no SPAN is executing)
+        let mut num_span_groups = 0;
+        let mut batch_offset = 0;
+        let mut batch: Option<[Felt; OP_BATCH_SIZE]> = None;
+
+        for row in 0..self.trace[0].len() {
+            let op_code = get_op_code(&self.trace[OP_BITS_RANGE], row);
+            let op = operation_from_op_code(op_code).ok_or(DecoderError::InvalidOpcode(op_code))?;
+
+            let group_count = self.trace[GROUP_COUNT_COL_IDX][row].as_int() as usize;
+
+            let op = match op {
+                Operation::Halt => break,
+                Operation::Span | Operation::Respan => {
+                    if op == Operation::Span {
+                        num_span_groups = group_count;
+                        batch_offset = 0;
+                    } else {
+                        batch_offset += OP_BATCH_SIZE;
+                    }
+                    batch = Some(core::array::from_fn(|i| {
+                        self.trace[HASHER_STATE_RANGE.start + i][row]
+                    }));
+                    op
+                },
+                Operation::End => {
+                    batch = None;
+                    op
+                },
+                // an immediate value is located in the group following the group being executed;
+                // its index is determined by the number of groups left in the SPAN
+                Operation::Push(_) | Operation::Emit(_) => {
+                    let imm_value = num_span_groups
+                        .checked_sub(group_count)
+                        .and_then(|idx| idx.checked_sub(batch_offset))
+                        .and_then(|idx| batch.as_ref()?.get(idx).copied())
+                        .ok_or(DecoderError::InvalidTraceStructure(row))?;
+                    match op {
+                        Operation::Push(_) => Operation::Push(imm_value),
+                        _ => Operation::Emit(imm_value.as_int() as u32),
+                    }
+                },
+                op => op,
+            };
+            operations.push(op);
+        }
+
+        Ok(operations)
+    }
+
+    /// Reconstructs the program which was executed to generate this trace.
+    ///
+    /// The MAST of the program is rebuilt by matching block start rows with their END rows:
+    /// control flow blocks are rebuilt from their executed children, and basic blocks from the
+    /// operations returned by [DecoderTrace::to_operations()]. Parts of the program which were not
+    /// executed are replaced with external nodes carrying their hashes; these are the branches of
+    /// SPLIT blocks which were not taken and the bodies of loops which were never entered. Blocks
+    /// executed dynamically by DYN and DYNCALL blocks are not part of the program, and thus, are
+    /// skipped.
+    ///
+    /// The hash of every rebuilt block is checked against the hash recorded in its END row, and
+    /// thus, the returned program has the same hash as the program which was executed.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - A row of the trace does not contain a valid opcode.
+    /// - An operation does not fit into the block structure of the trace, e.g., an END operation
+    ///   does not end any block, or a user operation is executed outside of a basic block.
+    /// - The trace ends before all started blocks have been ended.
+    /// - The hash of a rebuilt block does not match the hash recorded in the trace.
+    pub fn to_program(&self) -> Result<Program, DecoderError> {
+        let operations = self.to_operations()?;
+
+        let mut mast_forest = MastForest::new();
+        let mut frames: Vec<BlockFrame> = Vec::new();
+        let mut root = None;
+
+        for (row, &op) in operations.iter().enumerate() {
+            match op {
+                Operation::Join
+                | Operation::Split
+                | Operation::Loop
+                | Operation::Call
+                | Operation::SysCall
+                | Operation::Dyn
+                | Operation::Dyncall
+                | Operation::Span => {
+                    let frame = match frames.last() {
+                        // the program consists of a single root block, and basic blocks cannot
+                        // contain other blocks
+                        None if root.is_some() => {
+                            return Err(DecoderError::InvalidTraceStructure(row));
+                        },
+                        Some(BlockFrame::Span(_)) => {
+                            return Err(DecoderError::InvalidTraceStructure(row));
+                        },
+                        // blocks executed dynamically or in repeated iterations of a loop are
+                        // not rebuilt
+                        Some(BlockFrame::Skipped | BlockFrame::Dyn { .. })
+                        | Some(BlockFrame::Loop { body: Some(_), .. }) => BlockFrame::Skipped,
+                        _ => BlockFrame::new(op, self.child_hashes(row)),
+                    };
+                    frames.push(frame);
+                },
+                Operation::End => {
+                    let frame = frames.pop().ok_or(DecoderError::InvalidTraceStructure(row))?;
+                    let Some(node_id) = frame.build(&mut mast_forest, row)? else {
+                        continue;
+                    };
+                    if mast_forest[node_id].digest() != self.child_hashes(row)[0] {
+                        return Err(DecoderError::BlockHashMismatch(row));
+                    }
+
+                    match frames.last_mut() {
+                        Some(parent) => parent.add_child(node_id, row)?,
+                        None => root = Some(node_id),
+                    }
+                },
+                Operation::Repeat | Operation::Respan => (),
+                op => match frames.last_mut() {
+                    Some(BlockFrame::Span(ops)) => ops.push(op),
+                    Some(BlockFrame::Skipped) => (),
+                    _ => return Err(DecoderError::InvalidTraceStructure(row)),
+                },
+            }
+        }
+
+        if !frames.is_empty() {
+            return Err(DecoderError::UnfinishedCodeBlocks(frames.len()));
+        }
+        let root = root.ok_or(DecoderError::InvalidTraceStructure(operations.len()))?;
+        mast_forest.make_root(root);
+
+        Ok(Program::new(mast_forest.into(), root))
+    }
+
+    /// Shifts all block addresses in this trace so that the address of the program's root block
+    /// becomes `new_base`.
+    ///
+    /// Block addresses are stored in the block address column, and for rows executing user
+    /// operations, the address of the parent block is also stored in the second hasher state
+    /// column. All non-zero addresses (including the addresses of SPAN blocks incremented by
+    /// RESPAN operations) are shifted by the same amount, and thus, relations between blocks are
+    /// preserved. ZERO addresses denote the absence of a block (e.g., the parent of the root
+    /// block, or the HALT rows) and are left unchanged.
+    pub fn rebase_addresses(&mut self, new_base: Felt) {
+        let halt_opcode = Operation::Halt.op_code();
+        let trace_len = self.trace[ADDR_COL_IDX].len();
+
+        // the last row before the HALT rows contains the END operation of the root block, and
+        // thus, the address of the root block
+        let Some(root_row) = (0..trace_len)
+            .rev()
+            .find(|&row| get_op_code(&self.trace[OP_BITS_RANGE], row) != halt_opcode)
+        else {
+            return;
+        };
+        let delta = new_base - self.trace[ADDR_COL_IDX][root_row];
+
+        let parent_addr_col_idx = HASHER_STATE_RANGE.start + 1;
+        for row in 0..trace_len {
+            let addr = &mut self.trace[ADDR_COL_IDX][row];
+            if *addr != ZERO {
+                *addr += delta;
+            }
+
+            if self.trace[IN_SPAN_COL_IDX][row] == ONE {
+                let parent_addr = &mut self.trace[parent_addr_col_idx][row];
+                if *parent_addr != ZERO {
+                    *parent_addr += delta;
+                }
+            }
+        }
+    }
+
+    /// Returns the row indexes and kinds of obviously redundant operation sequences in this trace.
+    ///
+    /// A sequence is reported at the row of its first operation. Only operations executed in
+    /// consecutive rows are considered, and thus, sequences which are split by a RESPAN operation
+    /// or by NOOPs inserted at the end of an operation batch are not detected. Sequences spanning
+    /// two operation groups of the same batch are detected since no rows separate them.
+    ///
+    /// # Errors
+    /// Returns an error if a row of the trace does not contain a valid opcode.
+    pub fn find_redundant_ops(&self) -> Result<Vec<(usize, RedundancyKind)>, DecoderError> {
+        // every row of the trace up to the HALT rows contains exactly one operation, and thus,
+        // the index of an operation is also the index of its row
+        let operations = self.to_operations()?;
+
+        let redundant_ops = operations
+            .windows(2)
+            .enumerate()
+            .filter_map(|(row, ops)| RedundancyKind::of(ops[0], ops[1]).map(|kind| (row, kind)))
+            .collect();
+
+        Ok(redundant_ops)
+    }
+
+    /// Returns the number of times each user operation was immediately followed by another user
+    /// operation, keyed by the opcodes of the two operations.
+    ///
+    /// Only user operations executed in consecutive rows are counted, and thus, transitions across
+    /// RESPAN operations and other control flow operations are not included. NOOPs inserted to pad
+    /// operation groups and batches are executed as user operations, and are counted as such.
+    pub fn op_transition_counts(&self) -> BTreeMap<(u8, u8), usize> {
+        let in_span = &self.trace[IN_SPAN_COL_IDX];
+        let op_bits = &self.trace[OP_BITS_RANGE];
+        let mut counts = BTreeMap::new();
+        for row in 1..in_span.len() {
+            if in_span[row - 1] == ONE && in_span[row] == ONE {
+                let transition = (get_op_code(op_bits, row - 1), get_op_code(op_bits, row));
+                *counts.entry(transition).or_default() += 1;
+            }
+        }
+
+        counts
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the values of the specified row separated by spaces.
+    fn format_row(&self, row: usize) -> String {
+        self.trace.iter().map(|column| format!(" {}", column[row].as_int())).collect()
+    }
+
+    /// Returns the two words stored in the hasher state columns at the specified row.
+    ///
+    /// For rows starting a control flow block, these are the hashes of the block's children; for
+    /// END rows, the first word is the hash of the ended block.
+    fn child_hashes(&self, row: usize) -> [Digest; 2] {
+        let word = |offset: usize| {
+            let start = HASHER_STATE_RANGE.start + offset;
+            Digest::new([
+                self.trace[start][row],
+                self.trace[start + 1][row],
+                self.trace[start + 2][row],
+                self.trace[start + 3][row],
+            ])
+        };
+
+        [word(0), word(4)]
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the operation with the specified opcode, or None if the opcode is not a valid opcode.
+///
+/// Immediate values and error codes cannot be recovered from an opcode alone, and thus, are set to
+/// 0 for operations which carry them.
+pub fn operation_from_op_code(op_code: u8) -> Option<Operation> {
+    // opcodes rejected by Operation::from_op_code() (i.e., control flow operations and operations
+    // with associated data) are decoded by deserializing the opcode followed by zeroed associated
+    // data; this way, opcodes are mapped to operations only in vm_core. the longest associated
+    // data (i.e., the value of a PUSH operation) takes up 8 bytes.
+    Operation::from_op_code(op_code).or_else(|| {
+        let mut bytes = [0_u8; 9];
+        bytes[0] = op_code;
+        Operation::read_from(&mut SliceReader::new(&bytes)).ok()
+    })
+}
+
+// BLOCK FRAME
+// ================================================================================================
+
+/// A block which was started but not yet ended while reconstructing a program from its trace.
+enum BlockFrame {
+    Join(Vec<MastNodeId>),
+    Split {
+        on_true: Digest,
+        on_false: Digest,
+        taken: Option<MastNodeId>,
+    },
+    Loop {
+        body_hash: Digest,
+        body: Option<MastNodeId>,
+    },
+    Call {
+        is_syscall: bool,
+        callee: Option<MastNodeId>,
+    },
+    Dyn {
+        is_dyncall: bool,
+    },
+    Span(Vec<Operation>),
+    /// A block which is not part of the reconstructed program.
+    Skipped,
+}
+
+impl BlockFrame {
+    /// Returns a new frame for a block started by the specified operation with the specified
+    /// child hashes.
+    fn new(op: Operation, [child1_hash, child2_hash]: [Digest; 2]) -> Self {
+        match op {
+            Operation::Join => Self::Join(Vec::new()),
+            Operation::Split => Self::Split {
+                on_true: child1_hash,
+                on_false: child2_hash,
+                taken: None,
+            },
+            Operation::Loop => Self::Loop { body_hash: child1_hash, body: None },
+            Operation::Call => Self::Call { is_syscall: false, callee: None },
+            Operation::SysCall => Self::Call { is_syscall: true, callee: None },
+            Operation::Dyn => Self::Dyn { is_dyncall: false },
+            Operation::Dyncall => Self::Dyn { is_dyncall: true },
+            Operation::Span => Self::Span(Vec::new()),
+            _ => unreachable!("{op} does not start a block"),
+        }
+    }
+
+    /// Records a child of this block which was ended at the specified row.
+    fn add_child(&mut self, node_id: MastNodeId, row: usize) -> Result<(), DecoderError> {
+        match self {
+            Self::Join(children) if children.len() < 2 => children.push(node_id),
+            Self::Split { taken: child @ None, .. }
+            | Self::Loop { body: child @ None, .. }
+            | Self::Call { callee: child @ None, .. } => *child = Some(node_id),
+            _ => return Err(DecoderError::InvalidTraceStructure(row)),
+        }
+
+        Ok(())
+    }
+
+    /// Adds the node of this block ended at the specified row to the MAST forest, and returns its
+    /// ID; skipped blocks are not added.
+    fn build(
+        self,
+        mast_forest: &mut MastForest,
+        row: usize,
+    ) -> Result<Option<MastNodeId>, DecoderError> {
+        let missing_child = DecoderError::InvalidTraceStructure(row);
+        let node_id = match self {
+            Self::Join(children) => {
+                let &[first, second] = children.as_slice() else {
+                    return Err(missing_child);
+                };
+                mast_forest.add_join(first, second)
+            },
+            Self::Split { on_true, on_false, taken } => {
+                let taken = taken.ok_or(missing_child)?;
+                if mast_forest[taken].digest() == on_true {
+                    let on_false = mast_forest
+                        .add_external(on_false)
+                        .map_err(DecoderError::InvalidMastForest)?;
+                    mast_forest.add_split(taken, on_false)
+                } else {
+                    let on_true = mast_forest
+                        .add_external(on_true)
+                        .map_err(DecoderError::InvalidMastForest)?;
+                    mast_forest.add_split(on_true, taken)
+                }
+            },
+            Self::Loop { body_hash, body } => {
+                let body = match body {
+                    Some(body) => body,
+                    None => mast_forest
+                        .add_external(body_hash)
+                        .map_err(DecoderError::InvalidMastForest)?,
+                };
+                mast_forest.add_loop(body)
+            },
+            Self::Call { is_syscall, callee } => {
+                let callee = callee.ok_or(missing_child)?;
+                if is_syscall {
+                    mast_forest.add_syscall(callee)
+                } else {
+                    mast_forest.add_call(callee)
+                }
+            },
+            Self::Dyn { is_dyncall: false } => mast_forest.add_dyn(),
+            Self::Dyn { is_dyncall: true } => mast_forest.add_dyncall(),
+            Self::Span(ops) => mast_forest.add_block(ops, None),
+            Self::Skipped => return Ok(None),
+        };
+
+        node_id.map(Some).map_err(DecoderError::InvalidMastForest)
+    }
+}
+
+// REDUNDANCY KIND
+// ================================================================================================
+
+/// A pair of consecutive operations which leaves the VM in the same state as if neither of them
+/// had been executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedundancyKind {
+    /// An item pushed onto the stack by PAD, PUSH, or DUP is immediately dropped.
+    PushDrop,
+    /// The same items of the stack are swapped twice (SWAP, SWAPW, or SWAPDW).
+    DoubleSwap,
+    /// An element is negated twice by NEG.
+    ///
+    /// Pairs of NOT operations are not reported: NOT fails on a non-binary operand, and thus,
+    /// removing such a pair could remove a failure.
+    DoubleNegation,
+}
+
+impl RedundancyKind {
+    /// Returns the kind of redundancy of executing `op` followed by `next_op`, if any.
+    fn of(op: Operation, next_op: Operation) -> Option<Self> {
+        match (op, next_op) {
+            (
+                Operation::Pad
+                | Operation::Push(_)
+                | Operation::Dup0
+                | Operation::Dup1
+                | Operation::Dup2
+                | Operation::Dup3
+                | Operation::Dup4
+                | Operation::Dup5
+                | Operation::Dup6
+                | Operation::Dup7
+                | Operation::Dup9
+                | Operation::Dup11
+                | Operation::Dup13
+                | Operation::Dup15,
+                Operation::Drop,
+            ) => Some(Self::PushDrop),
+            (Operation::Swap, Operation::Swap)
+            | (Operation::SwapW, Operation::SwapW)
+            | (Operation::SwapDW, Operation::SwapDW) => Some(Self::DoubleSwap),
+            (Operation::Neg, Operation::Neg) => Some(Self::DoubleNegation),
+            _ => None,
+        }
+    }
+}
@@ -3,7 +3,7 @@ use core::error::Error;
 
 use miden_air::RowIndex;
 use vm_core::{
-    mast::{DecoratorId, MastNodeId},
+    mast::{DecoratorId, MastForestError, MastNodeId},
     stack::MIN_STACK_DEPTH,
     utils::to_hex,
 };
@@ -33,6 +33,8 @@ pub enum ExecutionError {
     CircularExternalNode(Digest),
     #[error("exceeded the allowed number of max cycles {0}")]
     CycleLimitExceeded(u32),
+    #[error("failed to decode program: {0}")]
+    DecoderError(DecoderError),
     #[error("decorator id {decorator_id} does not exist in MAST forest")]
     DecoratorNotFoundInForest { decorator_id: DecoratorId },
     #[error("division by zero at clock cycle {0}")]
@@ -139,12 +141,56 @@ pub enum ExecutionError {
     SyscallTargetNotInKernel(Digest),
 }
 
+impl From<DecoderError> for ExecutionError {
+    fn from(value: DecoderError) -> Self {
+        Self::DecoderError(value)
+    }
+}
+
 impl From<Ext2InttError> for ExecutionError {
     fn from(value: Ext2InttError) -> Self {
         Self::Ext2InttError(value)
     }
 }
 
+// DECODER ERROR
+// ================================================================================================
+
+#[derive(Debug, thiserror::Error)]
+pub enum DecoderError {
+    #[error("hash of the block ended at row {0} does not match the hash of its contents")]
+    BlockHashMismatch(usize),
+    #[error("no code block is currently being executed")]
+    EmptyBlockStack,
+    #[error("SPAN block ended before all of its operations were executed ({num_groups_left} op groups left, current op group {group_ops_left})")]
+    IncompleteSpanBlock {
+        group_ops_left: Felt,
+        num_groups_left: Felt,
+    },
+    #[error("failed to build MAST forest")]
+    InvalidMastForest(#[source] MastForestError),
+    #[error("decoder trace contains an invalid opcode {0}")]
+    InvalidOpcode(u8),
+    #[error("operation at row {0} of the decoder trace does not fit into its block structure")]
+    InvalidTraceStructure(usize),
+    #[error("loop body was repeated more than {0} times")]
+    LoopIterationLimitExceeded(usize),
+    #[error("code block nesting depth cannot exceed {0}")]
+    MaxNestingExceeded(usize),
+    #[error("operation requires a SPAN block, but no SPAN block is currently being executed")]
+    NotInSpan,
+    #[error("operation group value {0} encodes more operations than fit into a single group")]
+    OpGroupOverflow(Felt),
+    #[error("decoded program has hash {actual}, but expected {expected}")]
+    ProgramHashMismatch { expected: Digest, actual: Digest },
+    #[error("trace length {trace_len} is smaller than the minimum required length {min_len}")]
+    TraceLenTooSmall { trace_len: usize, min_len: usize },
+    #[error("decoding ended with {0} code blocks which were started but not ended")]
+    UnfinishedCodeBlocks(usize),
+    #[error("decoding ended inside a SPAN block")]
+    UnfinishedSpanBlock,
+}
+
 // EXT2INTT ERROR
 // ================================================================================================
 
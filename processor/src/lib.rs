@@ -38,7 +38,7 @@ use system::System;
 pub use system::{ContextId, FMP_MIN, SYSCALL_FMP_MIN};
 
 mod decoder;
-use decoder::Decoder;
+pub use decoder::{Decoder, DecoderCall, DecoderOpLog, OpGroup, RedundancyKind, TraceProfile};
 
 mod stack;
 use stack::Stack;
@@ -60,7 +60,7 @@ use trace::TraceFragment;
 pub use trace::{ChipletsLengths, ExecutionTrace, TraceLenSummary, NUM_RAND_ROWS};
 
 mod errors;
-pub use errors::{ExecutionError, Ext2InttError};
+pub use errors::{DecoderError, ExecutionError, Ext2InttError};
 
 pub mod utils;
 
@@ -214,7 +214,10 @@ impl Process {
         let in_debug_mode = execution_options.enable_debugging();
         Self {
             system: System::new(execution_options.expected_cycles() as usize),
-            decoder: Decoder::new(in_debug_mode),
+            decoder: Decoder::new(in_debug_mode)
+                .with_max_nesting_depth(execution_options.max_block_nesting_depth())
+                .with_max_loop_repeats(execution_options.max_loop_repeats())
+                .with_op_log(execution_options.enable_decoder_op_log()),
             stack: Stack::new(&stack, execution_options.expected_cycles() as usize, in_debug_mode),
             range: RangeChecker::new(),
             chiplets: Chiplets::new(kernel),
@@ -365,7 +368,7 @@ impl Process {
             // longer ONE; each iteration of the loop is preceded by executing REPEAT operation
             // which drops the condition from the stack
             while self.stack.peek() == ONE {
-                self.decoder.repeat();
+                self.decoder.repeat()?;
                 self.execute_op(Operation::Drop, host)?;
                 self.execute_mast_node(node.body(), program, host)?;
             }
@@ -473,7 +476,7 @@ impl Process {
         // preceded by a RESPAN operation; executing RESPAN operation does not change the state
         // of the stack
         for op_batch in basic_block.op_batches().iter().skip(1) {
-            self.respan(op_batch);
+            self.respan(op_batch)?;
             self.execute_op(Operation::Noop, host)?;
             self.execute_op_batch(op_batch, &mut decorator_ids, op_offset, program, host)?;
             op_offset += op_batch.ops().len();
@@ -530,7 +533,7 @@ impl Process {
             }
 
             // decode and execute the operation
-            self.decoder.execute_user_op(op, op_idx);
+            self.decoder.execute_user_op(op, op_idx)?;
             self.execute_op(op, host)?;
 
             // if the operation carries an immediate value, the value is stored at the next group
@@ -550,7 +553,7 @@ impl Process {
                     // is enough room in the group to execute a NOOP (if there isn't, there is a
                     // bug somewhere in the assembler)
                     debug_assert!(op_idx < OP_GROUP_SIZE - 1, "invalid op index");
-                    self.decoder.execute_user_op(Operation::Noop, op_idx + 1);
+                    self.decoder.execute_user_op(Operation::Noop, op_idx + 1)?;
                     self.execute_op(Operation::Noop, host)?;
                 }
 
@@ -562,7 +565,7 @@ impl Process {
                 // if we haven't reached the end of the batch yet, set up the decoder for
                 // decoding the next operation group
                 if group_idx < num_batch_groups {
-                    self.decoder.start_op_group(batch.groups()[group_idx]);
+                    self.decoder.start_op_group(batch.groups()[group_idx])?;
                 }
             } else {
                 // if we are not at the end of the group, just increment the operation index
@@ -573,14 +576,14 @@ impl Process {
         // make sure we execute the required number of operation groups; this would happen when
         // the actual number of operation groups was not a power of two
         for group_idx in group_idx..num_batch_groups {
-            self.decoder.execute_user_op(Operation::Noop, 0);
+            self.decoder.execute_user_op(Operation::Noop, 0)?;
             self.execute_op(Operation::Noop, host)?;
 
             // if we are not at the last group yet, set up the decoder for decoding the next
             // operation groups. the groups were are processing are just NOOPs - so, the op group
             // value is ZERO
             if group_idx < num_batch_groups - 1 {
-                self.decoder.start_op_group(ZERO);
+                self.decoder.start_op_group(ZERO)?;
             }
         }
 
@@ -6,14 +6,18 @@ use miden_air::trace::{
     AUX_TRACE_RAND_ELEMENTS, AUX_TRACE_WIDTH, DECODER_TRACE_OFFSET, MIN_TRACE_LEN,
     STACK_TRACE_OFFSET, TRACE_WIDTH,
 };
-use vm_core::{stack::MIN_STACK_DEPTH, ProgramInfo, StackInputs, StackOutputs, ZERO};
+use vm_core::{
+    stack::MIN_STACK_DEPTH, Operation, ProgramInfo, StackInputs, StackOutputs, ONE, ZERO,
+};
 use winter_prover::{crypto::RandomCoin, EvaluationFrame, Trace, TraceInfo};
 
 use super::{
-    chiplets::AuxTraceBuilder as ChipletsAuxTraceBuilder, crypto::RpoRandomCoin,
-    decoder::AuxTraceBuilder as DecoderAuxTraceBuilder,
+    chiplets::AuxTraceBuilder as ChipletsAuxTraceBuilder,
+    crypto::RpoRandomCoin,
+    decoder::{operation_from_op_code, AuxTraceBuilder as DecoderAuxTraceBuilder},
     range::AuxTraceBuilder as RangeCheckerAuxTraceBuilder,
-    stack::AuxTraceBuilder as StackAuxTraceBuilder, ColMatrix, Digest, Felt, FieldElement, Process,
+    stack::AuxTraceBuilder as StackAuxTraceBuilder,
+    ColMatrix, Digest, Felt, FieldElement, Process,
 };
 
 mod utils;
@@ -64,6 +68,9 @@ impl ExecutionTrace {
     /// Number of rows at the end of an execution trace which are injected with random values.
     pub const NUM_RAND_ROWS: usize = NUM_RAND_ROWS;
 
+    /// Minimum number of rows in an execution trace; shorter traces are padded to this length.
+    pub const MIN_TRACE_LEN: usize = MIN_TRACE_LEN;
+
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Builds an execution trace for the provided process.
@@ -160,6 +167,53 @@ impl ExecutionTrace {
         &self.meta
     }
 
+    /// Checks that the stack depth recorded in the trace evolves according to the stack effects of
+    /// the executed operations.
+    ///
+    /// For every row, the depth of the stack in the next row is expected to be the depth in the
+    /// current row adjusted by the [Operation::stack_effect] of the operation executed in the
+    /// current row, but never less than [MIN_STACK_DEPTH]. The following rows are handled
+    /// differently:
+    /// - CALL, SYSCALL, and DYNCALL start a new execution context, and thus, the next row is
+    ///   expected to have the minimum stack depth.
+    /// - END of a LOOP block which was entered also drops the loop condition off the stack in the
+    ///   same row, and thus, decreases the depth of the stack by one.
+    /// - END of a CALL, SYSCALL, or DYNCALL block restores the stack depth of the caller, which
+    ///   cannot be determined from the current row alone; such rows are not checked.
+    ///
+    /// # Errors
+    /// Returns the index of the first row for which the stack depth in the next row does not match
+    /// the expected depth, or which does not contain a valid opcode.
+    pub fn verify_stack_depth_evolution(&self) -> Result<(), usize> {
+        for i in 0..self.last_step() {
+            let row = i.into();
+            let op_code = self.main_trace.get_op_code(row).as_int() as u8;
+            let op = operation_from_op_code(op_code).ok_or(i)?;
+
+            let depth = self.main_trace.stack_depth(row).as_int() as i64;
+            let expected_depth = match op {
+                Operation::Halt => return Ok(()),
+                Operation::Call | Operation::SysCall | Operation::Dyncall => MIN_STACK_DEPTH as i64,
+                Operation::End
+                    if self.main_trace.is_call_flag(row) == ONE
+                        || self.main_trace.is_syscall_flag(row) == ONE =>
+                {
+                    continue
+                },
+                Operation::End if self.main_trace.is_loop_flag(row) == ONE => {
+                    (depth - 1).max(MIN_STACK_DEPTH as i64)
+                },
+                _ => (depth + op.stack_effect() as i64).max(MIN_STACK_DEPTH as i64),
+            };
+
+            if self.main_trace.stack_depth((i + 1).into()).as_int() as i64 != expected_depth {
+                return Err(i);
+            }
+        }
+
+        Ok(())
+    }
+
     // HELPER METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -1,11 +1,15 @@
 use alloc::vec::Vec;
 
-use miden_air::trace::{AUX_TRACE_RAND_ELEMENTS, STACK_AUX_TRACE_OFFSET};
+use miden_air::trace::{
+    main_trace::MainTrace, stack::B0_COL_IDX, AUX_TRACE_RAND_ELEMENTS, STACK_AUX_TRACE_OFFSET,
+    STACK_TRACE_OFFSET, TRACE_WIDTH,
+};
 
 use super::{
-    build_trace_from_ops, rand_array, Felt, FieldElement, Operation, NUM_RAND_ROWS, ONE, ZERO,
+    build_trace_from_ops, build_trace_from_program, rand_array, Felt, FieldElement, MastForest,
+    Operation, Program, NUM_RAND_ROWS, ONE, ZERO,
 };
-use crate::stack::OverflowTableRow;
+use crate::{stack::OverflowTableRow, ColMatrix};
 
 // CONSTANTS
 // ================================================================================================
@@ -96,3 +100,56 @@ fn p1_trace() {
         assert_eq!(ONE, p1[i]);
     }
 }
+
+// STACK DEPTH TESTS
+// ================================================================================================
+
+#[test]
+fn verify_stack_depth_evolution() {
+    let ops = vec![
+        Operation::Pad,    // depth 16 -> 17, clk 1
+        Operation::Pad,    // depth 17 -> 18, clk 2
+        Operation::U32add, // depth remains 18, clk 3
+        Operation::Drop,   // depth 18 -> 17, clk 4
+        Operation::Drop,   // depth 17 -> 16, clk 5
+    ];
+    let init_stack = (1..17).collect::<Vec<_>>();
+    let mut trace = build_trace_from_ops(ops, &init_stack);
+    assert_eq!(Ok(()), trace.verify_stack_depth_evolution());
+
+    // record an incorrect stack depth after the second PAD
+    let mut columns = trace.main_trace.get_column_range(0..TRACE_WIDTH);
+    columns[STACK_TRACE_OFFSET + B0_COL_IDX][3] = Felt::new(17);
+    let last_program_row = trace.main_trace.last_program_row();
+    trace.main_trace = MainTrace::new(ColMatrix::new(columns), last_program_row);
+    assert_eq!(Err(2), trace.verify_stack_depth_evolution());
+}
+
+#[test]
+fn verify_stack_depth_evolution_loop_and_call() {
+    let program = {
+        let mut mast_forest = MastForest::new();
+
+        // the loop body leaves ZERO at the top of the stack, and thus, the loop is exited with the
+        // stack depth of 18; END of the loop then drops the condition
+        let loop_body_id =
+            mast_forest.add_block(vec![Operation::Pad, Operation::Pad], None).unwrap();
+        let loop_node_id = mast_forest.add_loop(loop_body_id).unwrap();
+
+        // the callee is executed with the stack depth of 16; END of the call restores depth 17
+        let callee_id = mast_forest.add_block(vec![Operation::Pad, Operation::Drop], None).unwrap();
+        let call_node_id = mast_forest.add_call(callee_id).unwrap();
+
+        // the last DROP makes sure the program ends with the stack depth of 16
+        let drop_id = mast_forest.add_block(vec![Operation::Drop], None).unwrap();
+        let call_and_drop_id = mast_forest.add_join(call_node_id, drop_id).unwrap();
+
+        let join_id = mast_forest.add_join(loop_node_id, call_and_drop_id).unwrap();
+        mast_forest.make_root(join_id);
+
+        Program::new(mast_forest.into(), join_id)
+    };
+
+    let trace = build_trace_from_program(&program, &[1]);
+    assert_eq!(Ok(()), trace.verify_stack_depth_evolution());
+}